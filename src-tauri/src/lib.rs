@@ -3,24 +3,59 @@ use sysinfo::System;
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
-use std::sync::Mutex;
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::process::Child;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct SystemStats {
     cpu: f32,
+    // EMA of the sampler's readings; None until the first sample lands
+    cpu_smoothed: Option<f32>,
+    // Index i is always core i
+    cpu_per_core: Vec<f32>,
     memory_used: u64,
     memory_total: u64,
     memory_percent: f32,
+    swap_used: u64,
+    swap_total: u64,
+    // 1, 5 and 15 minute load; zeros where the OS has no load average (Windows)
+    load_avg: [f64; 3],
+    load_avg_supported: bool,
     disk_used: u64,
     disk_total: u64,
     disk_percent: f32,
+    disk_mount: String,
+    process_count: usize,
+    // Only available where sysinfo exposes per-process tasks (Linux)
+    thread_count: Option<usize>,
+    // Bytes/sec averaged over the time since the previous get_system_stats call,
+    // summed over non-loopback interfaces
+    net_rx_bytes_per_sec: u64,
+    net_tx_bytes_per_sec: u64,
+    // (interface, rx bytes/sec, tx bytes/sec), same window
+    net_interfaces: Vec<(String, u64, u64)>,
+    uptime_secs: u64,
+    // Unix seconds
+    boot_time: u64,
 }
 
 #[derive(Serialize, Clone)]
 pub struct Task {
     text: String,
     done: bool,
+    // 0 for top-level tasks, 1 for their subtasks, ...
+    depth: u8,
+    // From a "(due: YYYY-MM-DD)" or "@due(YYYY-MM-DD)" tag, which is stripped from text
+    due: Option<chrono::NaiveDate>,
+    overdue: bool,
+    // 1 = highest, from "!high"-style or "(p1)"-style markers (also stripped from text)
+    priority: Option<u8>,
+    // "#word" tokens in the order written, without the "#"; see find_tags
+    tags: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -29,326 +64,2863 @@ pub struct Project {
     name: String,
     status: String,
     category: String,
+    // First line only, for cards; description_full has the whole section
     description: String,
+    description_full: String,
     task_count: usize,
     tasks_done: usize,
+    // 0-100, and 0 when there are no tasks
+    progress: f32,
+    // Has tasks and all of them are done
+    completed: bool,
     tasks: Vec<Task>,
+    // Project ids from "Blocked by:" / "Depends on:" lines
+    depends_on: Vec<String>,
+    // Lives in projects/archive
+    archived: bool,
+    // From YAML front matter when present
+    priority: Option<String>,
+    due: Option<String>,
+    tags: Vec<String>,
+    // Unix seconds of the file's last modification, 0 if the stat fails
+    modified: u64,
 }
 
 #[tauri::command]
-fn get_system_stats() -> SystemStats {
-    let mut sys = System::new_all();
-    sys.refresh_all();
-    
-    // CPU usage (average across all cores)
+fn get_system_stats(mount: Option<String>) -> SystemStats {
+    // CPU is kept fresh by the background sampler; memory and processes are refreshed here
+    let mut sys = shared_system().lock().unwrap();
+    sys.refresh_memory();
+    sys.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::All,
+        true,
+        sysinfo::ProcessRefreshKind::new(),
+    );
+
+    // CPU usage (average across all cores). shared_system takes two spaced refreshes at
+    // init and the sampler refreshes every SAMPLE_INTERVAL after that, so per-core values
+    // are real deltas, never the zeros of a first refresh.
     let cpu = sys.global_cpu_usage();
+    let cpu_per_core: Vec<f32> = sys.cpus().iter().map(|c| c.cpu_usage()).collect();
     
     // Memory
     let memory_total = sys.total_memory();
     let memory_used = sys.used_memory();
     let memory_percent = (memory_used as f32 / memory_total as f32) * 100.0;
+    let swap_used = sys.used_swap();
+    let swap_total = sys.total_swap();
+
+    // Load average
+    let load_avg_supported = !cfg!(target_os = "windows");
+    let load_avg = if load_avg_supported {
+        let load = System::load_average();
+        [load.one, load.five, load.fifteen]
+    } else {
+        [0.0; 3]
+    };
     
-    // Disk (root partition)
-    let disks = sysinfo::Disks::new_with_refreshed_list();
-    let (disk_used, disk_total) = disks
-        .iter()
-        .find(|d| d.mount_point() == std::path::Path::new("/"))
-        .map(|d| (d.total_space() - d.available_space(), d.total_space()))
-        .unwrap_or((0, 1));
+    // Disk: the requested mount, else the one holding the home directory (usually what
+    // the user cares about)
+    let (disk_mount, disk_used, disk_total) = select_disk(mount.as_deref())
+        .map(|d| (d.mount, d.used, d.total))
+        .unwrap_or_else(|| (String::new(), 0, 1));
     let disk_percent = (disk_used as f32 / disk_total as f32) * 100.0;
-    
+
+    // Processes (Linux lists threads as processes too, so skip those)
+    let processes: Vec<_> = sys.processes().values().filter(|p| p.thread_kind().is_none()).collect();
+    let process_count = processes.len();
+    let thread_count = processes
+        .iter()
+        .filter_map(|p| p.tasks().map(|t| t.len()))
+        .reduce(|a, b| a + b);
+
+    // Network
+    let net_interfaces = network_rates();
+    let net_rx_bytes_per_sec = net_interfaces.iter().map(|(_, rx, _)| rx).sum();
+    let net_tx_bytes_per_sec = net_interfaces.iter().map(|(_, _, tx)| tx).sum();
+
     SystemStats {
         cpu,
+        cpu_smoothed: *CPU_SMOOTHED.lock().unwrap(),
+        cpu_per_core,
         memory_used,
         memory_total,
         memory_percent,
+        swap_used,
+        swap_total,
+        load_avg,
+        load_avg_supported,
         disk_used,
         disk_total,
         disk_percent,
+        disk_mount,
+        process_count,
+        thread_count,
+        net_rx_bytes_per_sec,
+        net_tx_bytes_per_sec,
+        net_interfaces,
+        uptime_secs: System::uptime(),
+        boot_time: System::boot_time(),
     }
 }
 
+#[derive(Serialize)]
+pub struct ProcessInfo {
+    pid: u32,
+    name: String,
+    cpu: f32,
+    memory: u64,
+}
+
+// Busiest processes by "cpu" (the default) or "memory". CPU is measured against the
+// shared System's previous process refresh, so the very first call reads near zero.
 #[tauri::command]
-fn toggle_task(project_id: String, task_index: usize) -> Result<(), String> {
-    let home = std::env::var("HOME").unwrap_or_default();
-    let projects_dir = PathBuf::from(&home).join(".openclaw/workspace/projects");
-    let file_path = projects_dir.join(format!("{}.md", project_id));
+fn get_top_processes(sort_by: String, limit: usize) -> Vec<ProcessInfo> {
+    let mut sys = shared_system().lock().unwrap();
+    sys.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::All,
+        true,
+        sysinfo::ProcessRefreshKind::new().with_cpu().with_memory(),
+    );
 
-    let content = fs::read_to_string(&file_path)
-        .map_err(|e| format!("Failed to read project file: {}", e))?;
+    let mut processes: Vec<ProcessInfo> = sys
+        .processes()
+        .values()
+        .filter(|p| p.thread_kind().is_none() && p.status() != sysinfo::ProcessStatus::Zombie)
+        .filter_map(|p| {
+            let name = p.name().to_string_lossy().trim().to_string();
+            if name.is_empty() {
+                return None;
+            }
+            Some(ProcessInfo {
+                pid: p.pid().as_u32(),
+                name,
+                cpu: p.cpu_usage(),
+                memory: p.memory(),
+            })
+        })
+        .collect();
 
-    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
-    let mut task_num = 0;
-
-    for line in lines.iter_mut() {
-        let trimmed = line.trim();
-        if trimmed.starts_with("- [") {
-            if task_num == task_index {
-                if trimmed.starts_with("- [x]") || trimmed.starts_with("- [X]") {
-                    *line = line.replacen("- [x]", "- [ ]", 1).replacen("- [X]", "- [ ]", 1);
-                } else if trimmed.starts_with("- [ ]") {
-                    *line = line.replacen("- [ ]", "- [x]", 1);
-                }
-                break;
+    if sort_by == "memory" {
+        processes.sort_by_key(|p| std::cmp::Reverse(p.memory));
+    } else {
+        processes.sort_by(|a, b| b.cpu.total_cmp(&a.cpu));
+    }
+    processes.truncate(limit);
+    processes
+}
+
+// Background task emitting "system-stats"; at most one runs at a time
+static STATS_STREAM: Mutex<Option<tauri::async_runtime::JoinHandle<()>>> = Mutex::new(None);
+
+// Emit a "system-stats" event every interval_ms. Starting again replaces the running
+// stream (e.g. to change the interval) rather than adding a second loop.
+#[tauri::command]
+fn start_stats_stream(app: tauri::AppHandle, interval_ms: u64) {
+    let interval = Duration::from_millis(interval_ms.max(250));
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            // sysinfo calls block, so keep them off the async workers
+            if let Ok(stats) = tauri::async_runtime::spawn_blocking(|| get_system_stats(None)).await {
+                let _ = app.emit("system-stats", stats);
             }
-            task_num += 1;
         }
+    });
+    if let Some(previous) = STATS_STREAM.lock().unwrap().replace(handle) {
+        previous.abort();
+    }
+}
+
+#[tauri::command]
+fn stop_stats_stream() {
+    if let Some(handle) = STATS_STREAM.lock().unwrap().take() {
+        handle.abort();
     }
+}
 
-    fs::write(&file_path, lines.join("\n"))
-        .map_err(|e| format!("Failed to write project file: {}", e))?;
+// Networks plus when they were last refreshed, so each call can turn byte deltas into rates
+static NETWORKS: OnceLock<Mutex<(sysinfo::Networks, Instant)>> = OnceLock::new();
 
-    Ok(())
+// Per-interface (name, rx/sec, tx/sec) since the previous call, loopback excluded
+fn network_rates() -> Vec<(String, u64, u64)> {
+    let mut guard = NETWORKS
+        .get_or_init(|| Mutex::new((sysinfo::Networks::new_with_refreshed_list(), Instant::now())))
+        .lock()
+        .unwrap();
+    let (networks, last_refresh) = &mut *guard;
+
+    networks.refresh();
+    let secs = last_refresh.elapsed().as_secs_f64();
+    *last_refresh = Instant::now();
+    if secs <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut rates: Vec<(String, u64, u64)> = networks
+        .iter()
+        .filter(|(name, _)| !name.starts_with("lo"))
+        .map(|(name, data)| {
+            (
+                name.clone(),
+                (data.received() as f64 / secs) as u64,
+                (data.transmitted() as f64 / secs) as u64,
+            )
+        })
+        .collect();
+    rates.sort_by(|a, b| a.0.cmp(&b.0));
+    rates
+}
+
+// ─── Battery ─────────────────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct BatteryInfo {
+    percent: f32,
+    charging: bool,
+    // To empty while discharging, to full while charging; None while estimating
+    time_remaining_secs: Option<u64>,
 }
 
+// None on machines without a battery, so the UI can hide the widget. With several
+// batteries the first one is reported.
 #[tauri::command]
-fn get_projects() -> Vec<Project> {
-    let home = std::env::var("HOME").unwrap_or_default();
-    let projects_dir = PathBuf::from(&home).join(".openclaw/workspace/projects");
-    
-    let mut projects = Vec::new();
-    
-    if let Ok(entries) = fs::read_dir(&projects_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().map_or(false, |e| e == "md") {
-                if let Ok(content) = fs::read_to_string(&path) {
-                    let project = parse_project(&content, &path);
-                    projects.push(project);
+fn get_battery() -> Option<BatteryInfo> {
+    use battery::units::{ratio::percent, time::second};
+
+    let manager = battery::Manager::new().ok()?;
+    let battery = manager.batteries().ok()?.flatten().next()?;
+
+    // A full battery on AC counts as charging
+    let charging = matches!(battery.state(), battery::State::Charging | battery::State::Full);
+    let remaining = if charging { battery.time_to_full() } else { battery.time_to_empty() };
+    let time_remaining_secs = remaining
+        .map(|t| t.get::<second>() as u64)
+        .filter(|secs| *secs > 0);
+
+    Some(BatteryInfo {
+        percent: battery.state_of_charge().get::<percent>(),
+        charging,
+        time_remaining_secs,
+    })
+}
+
+// ─── GPU ─────────────────────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct GpuStats {
+    name: String,
+    utilization_percent: f32,
+    memory_used: u64,
+    memory_total: u64,
+}
+
+// One entry per GPU; empty when none is supported, so the UI can hide the widget.
+// Separate from get_system_stats because querying the driver isn't free.
+#[tauri::command]
+fn get_gpu_stats() -> Vec<GpuStats> {
+    gpu_stats()
+}
+
+// NVIDIA via NVML. libnvidia-ml is loaded at runtime, so machines without the driver
+// just get an empty list.
+#[cfg(not(target_os = "macos"))]
+fn gpu_stats() -> Vec<GpuStats> {
+    static NVML: OnceLock<Option<nvml_wrapper::Nvml>> = OnceLock::new();
+    let Some(nvml) = NVML.get_or_init(|| nvml_wrapper::Nvml::init().ok()) else {
+        return Vec::new();
+    };
+
+    (0..nvml.device_count().unwrap_or(0))
+        .filter_map(|i| {
+            let device = nvml.device_by_index(i).ok()?;
+            let memory = device.memory_info().ok()?;
+            Some(GpuStats {
+                name: device.name().unwrap_or_else(|_| format!("GPU {}", i)),
+                utilization_percent: device.utilization_rates().map_or(0.0, |u| u.gpu as f32),
+                memory_used: memory.used,
+                memory_total: memory.total,
+            })
+        })
+        .collect()
+}
+
+// Parses IOAccelerator entries from `ioreg`, whose PerformanceStatistics dictionary looks like
+// {"Device Utilization %"=12,"In use system memory"=123456,"Alloc system memory"=7890123,...}
+#[cfg(target_os = "macos")]
+fn gpu_stats() -> Vec<GpuStats> {
+    let Ok(output) = Command::new("ioreg").args(["-r", "-d", "1", "-w", "0", "-c", "IOAccelerator"]).output() else {
+        return Vec::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+    let number = |block: &str, key: &str| -> Option<u64> {
+        let start = block.find(&format!("\"{}\"=", key))? + key.len() + 3;
+        let digits: String = block[start..].chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse().ok()
+    };
+    let string = |block: &str, key: &str| -> Option<String> {
+        let start = block.find(&format!("\"{}\" = \"", key))? + key.len() + 6;
+        block[start..].split('"').next().map(|s| s.to_string())
+    };
+
+    stdout
+        .split("+-o ")
+        .filter(|block| block.contains("PerformanceStatistics"))
+        .map(|block| GpuStats {
+            name: string(block, "model").unwrap_or_else(|| "GPU".to_string()),
+            utilization_percent: number(block, "Device Utilization %").unwrap_or(0) as f32,
+            memory_used: number(block, "In use system memory").unwrap_or(0),
+            memory_total: number(block, "Alloc system memory").unwrap_or(0),
+        })
+        .collect()
+}
+
+// ─── Temperatures ────────────────────────────────────────────────────────────
+
+// Sensor list is discovered once; each call only re-reads the values
+static COMPONENTS: OnceLock<Mutex<sysinfo::Components>> = OnceLock::new();
+
+// (sensor label, °C) for every sensor sysinfo exposes, e.g. the CPU package. Empty where
+// the platform has none.
+#[tauri::command]
+fn get_temperatures() -> Vec<(String, f32)> {
+    let mut components = COMPONENTS
+        .get_or_init(|| Mutex::new(sysinfo::Components::new_with_refreshed_list()))
+        .lock()
+        .unwrap();
+    components.refresh();
+    components
+        .iter()
+        .map(|c| (c.label().to_string(), c.temperature()))
+        .filter(|(_, temp)| temp.is_finite())
+        .collect()
+}
+
+// ─── Nested system stats ─────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct CpuStats {
+    global: f32,
+    per_core: Vec<f32>,
+    // MHz, one entry per core
+    freq: Vec<u64>,
+}
+
+#[derive(Serialize)]
+pub struct MemoryStats {
+    used: u64,
+    total: u64,
+    available: u64,
+    swap_used: u64,
+    swap_total: u64,
+}
+
+#[derive(Serialize)]
+pub struct DiskStats {
+    name: String,
+    mount: String,
+    file_system: String,
+    used: u64,
+    total: u64,
+    percent: f32,
+    removable: bool,
+}
+
+#[derive(Serialize)]
+pub struct InterfaceStats {
+    name: String,
+    rx_bytes_per_sec: u64,
+    tx_bytes_per_sec: u64,
+}
+
+#[derive(Serialize)]
+pub struct NetworkStats {
+    // Rates since the previous stats call, summed over interfaces (loopback excluded),
+    // the same figures as get_system_stats' net_* fields
+    rx_bytes_per_sec: u64,
+    tx_bytes_per_sec: u64,
+    interfaces: Vec<InterfaceStats>,
+}
+
+#[derive(Serialize)]
+pub struct SystemStatsV2 {
+    cpu: CpuStats,
+    memory: MemoryStats,
+    disks: Vec<DiskStats>,
+    network: NetworkStats,
+    process_count: usize,
+}
+
+// Grouped variant of get_system_stats, read from the same shared System in one refresh
+#[tauri::command]
+fn get_system_stats_v2() -> SystemStatsV2 {
+    let mut sys = shared_system().lock().unwrap();
+    sys.refresh_memory();
+    sys.refresh_cpu_frequency();
+    sys.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::All,
+        true,
+        sysinfo::ProcessRefreshKind::new(),
+    );
+
+    let cpu = CpuStats {
+        global: sys.global_cpu_usage(),
+        per_core: sys.cpus().iter().map(|c| c.cpu_usage()).collect(),
+        freq: sys.cpus().iter().map(|c| c.frequency()).collect(),
+    };
+
+    let memory = MemoryStats {
+        used: sys.used_memory(),
+        total: sys.total_memory(),
+        available: sys.available_memory(),
+        swap_used: sys.used_swap(),
+        swap_total: sys.total_swap(),
+    };
+
+    let disks = sysinfo::Disks::new_with_refreshed_list()
+        .iter()
+        .map(|d| {
+            let total = d.total_space();
+            // Pseudo and quota-limited filesystems can report more available than total
+            let used = total.saturating_sub(d.available_space());
+            DiskStats {
+                name: d.name().to_string_lossy().to_string(),
+                mount: d.mount_point().to_string_lossy().to_string(),
+                file_system: d.file_system().to_string_lossy().to_string(),
+                used,
+                total,
+                percent: if total > 0 { used as f32 / total as f32 * 100.0 } else { 0.0 },
+                removable: d.is_removable(),
+            }
+        })
+        .collect();
+
+    let interfaces: Vec<InterfaceStats> = network_rates()
+        .into_iter()
+        .map(|(name, rx_bytes_per_sec, tx_bytes_per_sec)| InterfaceStats {
+            name,
+            rx_bytes_per_sec,
+            tx_bytes_per_sec,
+        })
+        .collect();
+    let network = NetworkStats {
+        rx_bytes_per_sec: interfaces.iter().map(|i| i.rx_bytes_per_sec).sum(),
+        tx_bytes_per_sec: interfaces.iter().map(|i| i.tx_bytes_per_sec).sum(),
+        interfaces,
+    };
+
+    let process_count = sys.processes().values().filter(|p| p.thread_kind().is_none()).count();
+
+    SystemStatsV2 {
+        cpu,
+        memory,
+        disks,
+        network,
+        process_count,
+    }
+}
+
+// ─── Background stats sampler ────────────────────────────────────────────────
+
+const CPU_HISTORY_LEN: usize = 120;
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+// Disk usage moves slowly: sample once a minute and keep an hour of history
+const DISK_SAMPLE_EVERY: u64 = 60;
+const DISK_HISTORY_LEN: usize = 60;
+
+static SYSTEM: OnceLock<Mutex<System>> = OnceLock::new();
+static CPU_HISTORY: Mutex<VecDeque<f32>> = Mutex::new(VecDeque::new());
+static CPU_SMOOTHED: Mutex<Option<f32>> = Mutex::new(None);
+// Weight of the newest sample in the CPU EMA; override with dashboard.cpuSmoothing (0-1]
+const DEFAULT_CPU_SMOOTHING: f32 = 0.3;
+static DISK_HISTORY: Mutex<VecDeque<(Instant, u64, u64)>> = Mutex::new(VecDeque::new());
+
+#[derive(Serialize)]
+pub struct DiskTrend {
+    mount: String,
+    used: u64,
+    total: u64,
+    samples: usize,
+    // None until there are two samples to compare
+    bytes_per_hour: Option<f64>,
+    // None when usage is flat or shrinking
+    hours_until_full: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct DiskInfo {
+    name: String,
+    mount: String,
+    used: u64,
+    total: u64,
+    percent: f32,
+}
+
+fn disk_info(d: &sysinfo::Disk) -> DiskInfo {
+    let total = d.total_space();
+    let used = total.saturating_sub(d.available_space());
+    DiskInfo {
+        name: d.name().to_string_lossy().to_string(),
+        mount: d.mount_point().to_string_lossy().to_string(),
+        used,
+        total,
+        percent: if total > 0 { used as f32 / total as f32 * 100.0 } else { 0.0 },
+    }
+}
+
+// Disk whose mount point is the longest prefix of the (symlink-resolved) home directory
+fn home_mount(disks: &sysinfo::Disks) -> Option<&sysinfo::Disk> {
+    let home = PathBuf::from(std::env::var("HOME").ok()?);
+    let home = fs::canonicalize(&home).unwrap_or(home);
+    disks
+        .iter()
+        .filter(|d| home.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().components().count())
+}
+
+fn home_disk() -> Option<DiskInfo> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    home_mount(&disks).map(disk_info)
+}
+
+// The disk mounted at `mount`, or the home disk when no mount is given. Falls back to the
+// largest disk when nothing matches, so callers always get a real volume.
+fn select_disk(mount: Option<&str>) -> Option<DiskInfo> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let wanted = match mount {
+        Some(mount) => disks.iter().find(|d| d.mount_point() == std::path::Path::new(mount)),
+        None => home_mount(&disks),
+    };
+    wanted
+        .or_else(|| disks.iter().max_by_key(|d| d.total_space()))
+        .map(disk_info)
+}
+
+#[tauri::command]
+fn get_home_disk() -> Result<DiskInfo, String> {
+    home_disk().ok_or_else(|| "No disk found for the home directory".to_string())
+}
+
+// One System shared across samples so CPU usage is measured against the previous refresh
+fn shared_system() -> &'static Mutex<System> {
+    SYSTEM.get_or_init(|| {
+        // Usage is a delta between refreshes, so take a second one after sysinfo's minimum
+        // interval; otherwise the first read after launch is all zeros
+        let mut sys = System::new();
+        sys.refresh_cpu_usage();
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_cpu_usage();
+        Mutex::new(sys)
+    })
+}
+
+fn start_stats_sampler() {
+    let alpha = dashboard_setting("cpuSmoothing")
+        .as_f64()
+        .map(|a| a as f32)
+        .filter(|a| *a > 0.0 && *a <= 1.0)
+        .unwrap_or(DEFAULT_CPU_SMOOTHING);
+
+    std::thread::spawn(move || {
+        let mut tick: u64 = 0;
+        loop {
+            if tick % DISK_SAMPLE_EVERY == 0 {
+                if let Some(DiskInfo { used, total, .. }) = select_disk(None) {
+                    let mut disk_history = DISK_HISTORY.lock().unwrap();
+                    if disk_history.len() == DISK_HISTORY_LEN {
+                        disk_history.pop_front();
+                    }
+                    disk_history.push_back((Instant::now(), used, total));
                 }
             }
+            tick += 1;
+
+            std::thread::sleep(SAMPLE_INTERVAL);
+            let cpu = {
+                let mut sys = shared_system().lock().unwrap();
+                sys.refresh_cpu_usage();
+                sys.global_cpu_usage()
+            };
+            {
+                let mut smoothed = CPU_SMOOTHED.lock().unwrap();
+                *smoothed = Some(match *smoothed {
+                    Some(prev) => alpha * cpu + (1.0 - alpha) * prev,
+                    None => cpu,
+                });
+            }
+            let mut history = CPU_HISTORY.lock().unwrap();
+            if history.len() == CPU_HISTORY_LEN {
+                history.pop_front();
+            }
+            history.push_back(cpu);
         }
+    });
+}
+
+// Last CPU_HISTORY_LEN global CPU samples, oldest first (one per second)
+#[tauri::command]
+fn get_cpu_history() -> Vec<f32> {
+    CPU_HISTORY.lock().unwrap().iter().copied().collect()
+}
+
+// Rate of change of usage on the home disk (the same one get_system_stats reports by
+// default) across the sampled window, for "disk filling" predictions
+#[tauri::command]
+fn get_disk_trend() -> DiskTrend {
+    let current = select_disk(None);
+    let history = DISK_HISTORY.lock().unwrap();
+    let (used, total) = history
+        .back()
+        .map(|&(_, used, total)| (used, total))
+        .or_else(|| current.as_ref().map(|d| (d.used, d.total)))
+        .unwrap_or((0, 0));
+
+    let bytes_per_hour = match (history.front(), history.back()) {
+        (Some(first), Some(last)) if history.len() >= 2 => {
+            let hours = last.0.duration_since(first.0).as_secs_f64() / 3600.0;
+            (hours > 0.0).then(|| (last.1 as f64 - first.1 as f64) / hours)
+        }
+        _ => None,
+    };
+    let hours_until_full = bytes_per_hour
+        .filter(|&rate| rate > 0.0)
+        .map(|rate| total.saturating_sub(used) as f64 / rate);
+
+    DiskTrend {
+        mount: current.map(|d| d.mount).unwrap_or_default(),
+        used,
+        total,
+        samples: history.len(),
+        bytes_per_hour,
+        hours_until_full,
     }
-    
-    // Sort by status (active first)
-    projects.sort_by(|a, b| {
-        let a_active = a.status.to_lowercase().contains("active");
-        let b_active = b.status.to_lowercase().contains("active");
-        b_active.cmp(&a_active)
+}
+
+fn projects_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_default();
+    PathBuf::from(&home).join(".openclaw/workspace/projects")
+}
+
+// Archived projects live here; get_projects skips them by default
+fn archive_dir() -> PathBuf {
+    projects_dir().join("archive")
+}
+
+// Resolve a project id to its markdown file, rejecting ids that could escape the projects dir
+fn project_path(project_id: &str) -> Result<PathBuf, String> {
+    if project_id.is_empty()
+        || project_id.starts_with('.')
+        || project_id.contains('/')
+        || project_id.contains('\\')
+    {
+        return Err(format!("Invalid project id: {}", project_id));
+    }
+    Ok(projects_dir().join(format!("{}.md", project_id)))
+}
+
+#[tauri::command]
+fn toggle_task(project_id: String, task_index: usize) -> Result<(), String> {
+    edit_project_lines(&project_id, |lines| {
+        let at = task_line_indices(lines).nth(task_index);
+        if let Some(at) = at {
+            toggle_checkbox(&mut lines[at]);
+        }
+        Ok(())
+    })
+}
+
+fn toggle_checkbox(line: &mut String) {
+    let trimmed = line.trim();
+    if trimmed.starts_with("- [x]") || trimmed.starts_with("- [X]") {
+        *line = line.replacen("- [x]", "- [ ]", 1).replacen("- [X]", "- [ ]", 1);
+    } else if trimmed.starts_with("- [ ]") {
+        *line = line.replacen("- [ ]", "- [x]", 1);
+    }
+}
+
+// Toggle the one task whose text equals `text`, either as written in the file or as
+// displayed (due/priority markers and trailing tags stripped), so it can't flip the wrong line if indices drift
+#[tauri::command]
+fn toggle_task_by_text(project_id: String, text: String) -> Result<(), String> {
+    let text = text.trim().to_string();
+    edit_project_lines(&project_id, |lines| {
+        let matching: Vec<usize> = task_line_indices(lines)
+            .filter(|&i| {
+                let Some(raw) = lines[i].trim().strip_prefix("- [").and_then(|r| r.get(2..)) else {
+                    return false;
+                };
+                let raw = raw.trim();
+                raw == text || parse_task_text(raw).0 == text
+            })
+            .collect();
+
+        match matching.as_slice() {
+            [i] => {
+                toggle_checkbox(&mut lines[*i]);
+                Ok(())
+            }
+            [] => Err(format!("No task matching \"{}\"", text)),
+            many => Err(format!("{} tasks match \"{}\"; toggle by index instead", many.len(), text)),
+        }
+    })
+}
+
+// Read a project file, let `edit` change its lines, and write it back. Every command
+// that modifies a project goes through here.
+fn edit_project_lines<T>(
+    project_id: &str,
+    edit: impl FnOnce(&mut Vec<String>) -> Result<T, String>,
+) -> Result<T, String> {
+    let file_path = project_path(project_id)?;
+
+    // Hold the file's lock across the whole read-modify-write so rapid edits don't interleave
+    let lock = project_lock(&file_path);
+    let _guard = lock.lock().unwrap();
+
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read project file: {}", e))?;
+
+    let style = line_style(&content);
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let result = edit(&mut lines)?;
+
+    write_atomic(&file_path, &join_lines(&lines, style))
+        .map_err(|e| format!("Failed to write project file: {}", e))?;
+
+    Ok(result)
+}
+
+// (dominant line ending, ends with a newline) of a file, so rewrites keep the original
+// CRLF/LF style instead of churning every line in a diff
+fn line_style(content: &str) -> (&'static str, bool) {
+    let crlf = content.matches("\r\n").count();
+    let lf = content.matches('\n').count() - crlf;
+    let eol = if crlf > lf { "\r\n" } else { "\n" };
+    (eol, content.ends_with('\n'))
+}
+
+fn join_lines(lines: &[String], (eol, trailing_newline): (&str, bool)) -> String {
+    let mut out = lines.join(eol);
+    if trailing_newline && !lines.is_empty() {
+        out.push_str(eol);
+    }
+    out
+}
+
+static PROJECT_LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
+
+// One lock per project file, shared by every command that rewrites it
+fn project_lock(path: &std::path::Path) -> Arc<Mutex<()>> {
+    PROJECT_LOCKS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap()
+        .entry(path.to_path_buf())
+        .or_default()
+        .clone()
+}
+
+// Write to a temp file in the same directory and rename it over the target, so a crash
+// leaves either the old or the new contents, never a truncated file
+fn write_atomic(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let tmp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let written = fs::File::create(&tmp_path).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
     });
-    
-    projects
+    if let Err(e) = written.and_then(|_| fs::rename(&tmp_path, path)) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    Ok(())
 }
 
-fn parse_project(content: &str, path: &PathBuf) -> Project {
-    let lines: Vec<&str> = content.lines().collect();
-    
-    // Get name from first H1 or filename
-    let name = lines.iter()
-        .find(|l| l.starts_with("# "))
-        .map(|l| l.trim_start_matches("# ").to_string())
-        .unwrap_or_else(|| {
-            path.file_stem()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_default()
-        });
-    
-    // Get status from "Status: X" line
-    let status = lines.iter()
-        .find(|l| l.to_lowercase().starts_with("status:"))
-        .map(|l| l.split(':').nth(1).unwrap_or("").trim().to_string())
-        .unwrap_or_else(|| "Unknown".to_string());
-    
-    // Get category from "Category: X" line
-    let category = lines.iter()
-        .find(|l| l.to_lowercase().starts_with("category:"))
-        .map(|l| l.split(':').nth(1).unwrap_or("").trim().to_string())
-        .unwrap_or_else(|| "personal".to_string());
-    
-    // Get description from ## Description section or first paragraph
-    let description = extract_section(content, "Description")
-        .or_else(|| {
-            lines.iter()
-                .skip_while(|l| l.starts_with('#') || l.starts_with("Status:") || l.starts_with("Created:") || l.starts_with("Priority:") || l.is_empty())
-                .next()
-                .map(|s| s.to_string())
-        })
-        .unwrap_or_default();
-    
-    // Extract tasks
-    let tasks: Vec<Task> = lines.iter()
-        .filter(|l| l.trim().starts_with("- ["))
+// Where new tasks go: after the last existing task so the list stays contiguous, else
+// under a "## Tasks" heading, else at the end of the file after a blank line
+fn task_insert_position(lines: &mut Vec<String>) -> usize {
+    if let Some(last) = task_line_indices(lines).last() {
+        return last + 1;
+    }
+    if let Some(heading) = lines.iter().position(|l| l.trim().eq_ignore_ascii_case("## tasks")) {
+        let skip_blank = lines.get(heading + 1).is_some_and(|l| l.trim().is_empty());
+        return heading + if skip_blank { 2 } else { 1 };
+    }
+    if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+        lines.push(String::new());
+    }
+    lines.len()
+}
+
+#[tauri::command]
+fn add_task(project_id: String, text: String) -> Result<(), String> {
+    // One call, one task: newlines would start new list items
+    let text = text.replace(['\r', '\n'], " ").trim().to_string();
+    if text.is_empty() {
+        return Err("Task text cannot be empty".to_string());
+    }
+
+    edit_project_lines(&project_id, |lines| {
+        let at = task_insert_position(lines);
+        lines.insert(at, format!("- [ ] {}", text));
+        Ok(())
+    })
+}
+
+// Indices of a project file's checkbox lines, after any front matter. The parser and every
+// task edit count tasks through this, so a task index means the same line everywhere.
+fn task_line_indices<S: AsRef<str>>(lines: &[S]) -> impl Iterator<Item = usize> + '_ {
+    lines
+        .iter()
+        .enumerate()
+        .skip(front_matter_lines(lines))
+        .filter(|(_, l)| l.as_ref().trim().starts_with("- ["))
+        .map(|(i, _)| i)
+}
+
+// Line index of the nth checkbox line
+fn task_line_index(lines: &[String], task_index: usize) -> Result<usize, String> {
+    task_line_indices(lines)
+        .nth(task_index)
+        .ok_or_else(|| format!("Task index {} out of range", task_index))
+}
+
+#[tauri::command]
+fn delete_task(project_id: String, task_index: usize) -> Result<(), String> {
+    edit_project_lines(&project_id, |lines| {
+        let at = task_line_index(lines, task_index)?;
+        lines.remove(at);
+        Ok(())
+    })
+}
+
+// Move a task to another position among the checkbox lines; headings and prose between
+// tasks stay where they are, only the task lines shift into each other's slots
+#[tauri::command]
+fn reorder_task(project_id: String, from_index: usize, to_index: usize) -> Result<(), String> {
+    edit_project_lines(&project_id, |lines| {
+        let slots: Vec<usize> = task_line_indices(lines).collect();
+        for index in [from_index, to_index] {
+            if index >= slots.len() {
+                return Err(format!("Task index {} out of range", index));
+            }
+        }
+
+        let mut tasks: Vec<String> = slots.iter().map(|&i| lines[i].clone()).collect();
+        let moved = tasks.remove(from_index);
+        tasks.insert(to_index, moved);
+        for (slot, task) in slots.into_iter().zip(tasks) {
+            lines[slot] = task;
+        }
+        Ok(())
+    })
+}
+
+// Replace a task's text, keeping its indentation and checkbox state
+#[tauri::command]
+fn edit_task(project_id: String, task_index: usize, new_text: String) -> Result<(), String> {
+    let new_text = new_text.replace(['\r', '\n'], " ").trim().to_string();
+    if new_text.is_empty() {
+        return Err("Task text cannot be empty".to_string());
+    }
+
+    edit_project_lines(&project_id, |lines| {
+        let at = task_line_index(lines, task_index)?;
+        let line = &lines[at];
+        // "- [" is present (that's how the line was found); keep everything through "]"
+        let open = line.find("- [").unwrap_or(0);
+        let close = line[open..].find(']').map(|i| open + i + 1).unwrap_or(line.len());
+        lines[at] = format!("{} {}", &line[..close], new_text);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+fn add_tasks_bulk(project_id: String, text: String) -> Result<usize, String> {
+    // One task per non-empty line; keep existing checkboxes, strip plain bullets
+    let new_tasks: Vec<String> = text
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
         .map(|l| {
-            let trimmed = l.trim();
-            let done = trimmed.starts_with("- [x]") || trimmed.starts_with("- [X]");
-            let text = trimmed
-                .trim_start_matches("- [x] ")
-                .trim_start_matches("- [X] ")
-                .trim_start_matches("- [ ] ")
-                .to_string();
-            Task { text, done }
+            if l.starts_with("- [ ]") || l.starts_with("- [x]") || l.starts_with("- [X]") {
+                l.to_string()
+            } else {
+                let l = l.strip_prefix("- ").or_else(|| l.strip_prefix("* ")).unwrap_or(l);
+                format!("- [ ] {}", l.trim())
+            }
         })
         .collect();
+
+    if new_tasks.is_empty() {
+        return Ok(0);
+    }
+
+    edit_project_lines(&project_id, |lines| {
+        let count = new_tasks.len();
+        let at = task_insert_position(lines);
+        lines.splice(at..at, new_tasks);
+        Ok(count)
+    })
+}
+
+// Byte range of an @due(...) or (due: ...) tag plus its date, or None for the date if
+// it's malformed
+fn find_due_tag(line: &str) -> Option<(usize, usize, Option<chrono::NaiveDate>)> {
+    let (start, prefix_len) = match (line.find("@due("), line.find("(due:")) {
+        (Some(at), _) => (at, "@due(".len()),
+        (None, Some(paren)) => (paren, "(due:".len()),
+        (None, None) => return None,
+    };
+    let close = start + line[start..].find(')')?;
+    let date = chrono::NaiveDate::parse_from_str(line[start + prefix_len..close].trim(), "%Y-%m-%d").ok();
+    Some((start, close + 1, date))
+}
+
+// Cut line[start..end] out of a task's text, closing up the gap it leaves
+fn remove_span(text: &str, start: usize, end: usize) -> String {
+    format!("{} {}", text[..start].trim_end(), text[end..].trim_start())
+        .trim()
+        .to_string()
+}
+
+// Byte range and level (1 = highest) of a priority marker: !high / !medium / !low or (p1)..(p9)
+fn find_priority_marker(text: &str) -> Option<(usize, usize, u8)> {
+    text.split_whitespace().find_map(|token| {
+        let level = match token.to_lowercase().as_str() {
+            "!high" => 1,
+            "!medium" | "!med" => 2,
+            "!low" => 3,
+            t => t
+                .strip_prefix("(p")
+                .and_then(|t| t.strip_suffix(')'))
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|n| (1..=9).contains(n))?,
+        };
+        let start = token.as_ptr() as usize - text.as_ptr() as usize;
+        Some((start, start + token.len(), level))
+    })
+}
+
+// Byte range and name of every "#word" token. A word is letters, digits, '-' and '_' (not
+// all digits, so "#42" issue refs aren't tags) and may be followed by punctuation. A task
+// can never be a heading, so a "#word" at the very start of the text counts too; "# word"
+// and "##word" don't
+fn find_tags(text: &str) -> Vec<(usize, usize, String)> {
+    text.split_whitespace()
+        .filter_map(|token| {
+            let name = token
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', ')'])
+                .strip_prefix('#')?;
+            let valid = !name.is_empty()
+                && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+                && !name.chars().all(|c| c.is_ascii_digit());
+            let start = token.as_ptr() as usize - text.as_ptr() as usize;
+            valid.then(|| (start, start + token.len(), name.to_string()))
+        })
+        .collect()
+}
+
+// Display text, due date, priority and tags for a task's text. Due and priority markers
+// are stripped wherever they are; tags only when they trail the text ("Fix login #web
+// #urgent" shows as "Fix login"), since one mid-sentence is part of what it says
+fn parse_task_text(raw: &str) -> (String, Option<chrono::NaiveDate>, Option<u8>, Vec<String>) {
+    let (text, due) = match find_due_tag(raw) {
+        Some((start, end, due)) => (remove_span(raw, start, end), due),
+        None => (raw.to_string(), None),
+    };
+    let (text, priority) = match find_priority_marker(&text) {
+        Some((start, end, priority)) => (remove_span(&text, start, end), Some(priority)),
+        None => (text, None),
+    };
+
+    let found = find_tags(&text);
+    let mut end = text.len();
+    for (start, stop, _) in found.iter().rev() {
+        if !text[*stop..end].trim().is_empty() {
+            break;
+        }
+        end = *start;
+    }
+    let mut tags: Vec<String> = Vec::new();
+    for (_, _, name) in found {
+        if !tags.contains(&name) {
+            tags.push(name);
+        }
+    }
+    // A task that's nothing but tags keeps them as its text
+    let text = match text[..end].trim_end() {
+        "" => text.clone(),
+        shown => shown.to_string(),
+    };
+    (text, due, priority, tags)
+}
+
+// Past its due date and still open
+fn is_overdue(done: bool, due: Option<chrono::NaiveDate>, today: chrono::NaiveDate) -> bool {
+    !done && due.is_some_and(|d| d < today)
+}
+
+#[tauri::command]
+fn defer_task(project_id: String, task_index: usize, days: i64) -> Result<String, String> {
+    let offset = chrono::TimeDelta::try_days(days)
+        .ok_or_else(|| format!("Invalid day offset: {}", days))?;
+    let today = chrono::Local::now().date_naive();
+
+    edit_project_lines(&project_id, |lines| {
+        let at = task_line_index(lines, task_index)?;
+        let line = &mut lines[at];
+
+        // Shift an existing due date; with no (or an unreadable) date, count from today
+        let tag = find_due_tag(line);
+        let base = tag.and_then(|(_, _, date)| date).unwrap_or(today);
+        let new_due = base
+            .checked_add_signed(offset)
+            .ok_or_else(|| format!("Day offset {} is out of range", days))?;
+        // Keep whichever tag style the line already uses
+        let new_tag = match tag {
+            Some((start, _, _)) if line[start..].starts_with('(') => {
+                format!("(due: {})", new_due.format("%Y-%m-%d"))
+            }
+            _ => format!("@due({})", new_due.format("%Y-%m-%d")),
+        };
+
+        match tag {
+            Some((start, end, _)) => line.replace_range(start..end, &new_tag),
+            None => {
+                line.push(' ');
+                line.push_str(&new_tag);
+            }
+        }
+
+        Ok(new_due.format("%Y-%m-%d").to_string())
+    })
+}
+
+// Lowercase, hyphen-separated file stem for a project name ("My Project!" -> "my-project")
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+// New project file scaffolded from name/category/description; returns its id
+#[tauri::command]
+fn create_project(name: String, category: String, description: String) -> Result<String, String> {
+    use std::io::Write;
+
+    let name = name.replace(['\r', '\n'], " ").trim().to_string();
+    if name.is_empty() {
+        return Err("Project name cannot be empty".to_string());
+    }
+    let id = slugify(&name);
+    if id.is_empty() {
+        return Err(format!("Cannot derive a file name from \"{}\"", name));
+    }
+    let category = category.replace(['\r', '\n'], " ").trim().to_string();
+    let category = if category.is_empty() { "personal".to_string() } else { category };
+
+    let content = format!(
+        "# {}\n\nStatus: active\nCategory: {}\n\n## Description\n\n{}\n\n## Tasks\n\n",
+        name,
+        category,
+        description.trim()
+    );
+
+    fs::create_dir_all(projects_dir())
+        .map_err(|e| format!("Failed to create projects dir: {}", e))?;
+    // create_new fails instead of clobbering an existing project
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(project_path(&id)?)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => format!("A project with id \"{}\" already exists", id),
+            _ => format!("Failed to create project file: {}", e),
+        })?;
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write project file: {}", e))?;
+
+    Ok(id)
+}
+
+// Move a project file between the projects dir and projects/archive, never overwriting
+fn move_project(project_id: &str, from_dir: &std::path::Path, to_dir: &std::path::Path) -> Result<(), String> {
+    // project_path validates the id; the file name is the same in either directory
+    let file_name = project_path(project_id)?
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    let from = from_dir.join(&file_name);
+    let to = to_dir.join(&file_name);
+
+    if !from.exists() {
+        return Err(format!("Project \"{}\" not found", project_id));
+    }
+    if to.exists() {
+        return Err(format!("A project with id \"{}\" already exists in {}", project_id, to_dir.display()));
+    }
+    fs::create_dir_all(to_dir)
+        .map_err(|e| format!("Failed to create {}: {}", to_dir.display(), e))?;
+
+    let lock = project_lock(&from);
+    let _guard = lock.lock().unwrap();
+    fs::rename(&from, &to).map_err(|e| format!("Failed to move project file: {}", e))
+}
+
+#[tauri::command]
+fn archive_project(project_id: String) -> Result<(), String> {
+    move_project(&project_id, &projects_dir(), &archive_dir())
+}
+
+#[tauri::command]
+fn unarchive_project(project_id: String) -> Result<(), String> {
+    move_project(&project_id, &archive_dir(), &projects_dir())
+}
+
+#[tauri::command]
+fn rename_project(project_id: String, new_name: String) -> Result<String, String> {
+    let new_name = new_name.replace(['\r', '\n'], " ").trim().to_string();
+    if new_name.is_empty() {
+        return Err("Project name cannot be empty".to_string());
+    }
+    let new_id = slugify(&new_name);
+    if new_id.is_empty() {
+        return Err(format!("Cannot derive a file name from \"{}\"", new_name));
+    }
+
+    let old_path = project_path(&project_id)?;
+    let new_path = project_path(&new_id)?;
+
+    let lock = project_lock(&old_path);
+    let _guard = lock.lock().unwrap();
+
+    let content = fs::read_to_string(&old_path)
+        .map_err(|e| format!("Failed to read project file: {}", e))?;
+
+    // Replace the H1, or add one if the file never had a title. Either way it goes below
+    // any front matter, which must stay the first thing in the file to be recognized
+    let style = line_style(&content);
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let body_start = front_matter_lines(&lines);
+    match lines.iter().skip(body_start).position(|l| l.starts_with("# ")) {
+        Some(i) => lines[body_start + i] = format!("# {}", new_name),
+        None => lines.insert(body_start, format!("# {}", new_name)),
+    }
+
+    // Stage the new content next to the target so nothing is lost if we die mid-way
+    let tmp_path = projects_dir().join(format!(".{}.md.tmp", new_id));
+    fs::write(&tmp_path, join_lines(&lines, style))
+        .map_err(|e| format!("Failed to write project file: {}", e))?;
+
+    if new_id == project_id {
+        return fs::rename(&tmp_path, &new_path)
+            .map(|_| new_id)
+            .map_err(|e| format!("Failed to update project file: {}", e));
+    }
+
+    // A case-only change names the same file on a case-insensitive filesystem (the macOS
+    // default), where hard_link below would report it as taken. Move the old file aside
+    // and rename into place instead
+    if new_id.eq_ignore_ascii_case(&project_id) {
+        let aside = projects_dir().join(format!(".{}.md.old", project_id));
+        if let Err(e) = fs::rename(&old_path, &aside) {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(format!("Failed to rename project file: {}", e));
+        }
+        // Still present with the old file moved away: a separate file on a
+        // case-sensitive filesystem
+        let result = if new_path.exists() {
+            Err(format!("A project with id \"{}\" already exists", new_id))
+        } else {
+            fs::rename(&tmp_path, &new_path).map_err(|e| format!("Failed to rename project file: {}", e))
+        };
+        if result.is_err() {
+            let _ = fs::rename(&aside, &old_path);
+            let _ = fs::remove_file(&tmp_path);
+        } else {
+            let _ = fs::remove_file(&aside);
+        }
+        return result.map(|_| new_id);
+    }
+
+    // hard_link refuses to replace an existing file, so the existence check and the
+    // move happen in one step; the old file is only removed once the new id is in place
+    let linked = fs::hard_link(&tmp_path, &new_path);
+    let _ = fs::remove_file(&tmp_path);
+    match linked {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            return Err(format!("A project with id \"{}\" already exists", new_id));
+        }
+        Err(e) => return Err(format!("Failed to create renamed project file: {}", e)),
+    }
+
+    fs::remove_file(&old_path)
+        .map_err(|e| format!("Renamed to {} but failed to remove old file: {}", new_id, e))?;
+
+    Ok(new_id)
+}
+
+// Optional category/status filters match the whole value, ignoring case and surrounding space.
+// Archived projects are left out unless include_archived is set.
+#[tauri::command]
+fn get_projects(
+    category: Option<String>,
+    status: Option<String>,
+    include_archived: Option<bool>,
+    sort: Option<String>,
+) -> Vec<Project> {
+    let normalize = |s: &str| s.trim().to_lowercase();
+    let category = category.as_deref().map(normalize);
+    let status = status.as_deref().map(normalize);
+
+    let mut all = load_projects();
+    if include_archived.unwrap_or(false) {
+        all.extend(load_projects_in(&archive_dir()));
+    }
+
+    let mut projects: Vec<Project> = all
+        .into_iter()
+        .filter(|p| category.as_ref().map_or(true, |c| normalize(&p.category) == *c))
+        .filter(|p| status.as_ref().map_or(true, |s| normalize(&p.status) == *s))
+        .collect();
     
-    let task_count = tasks.len();
-    let tasks_done = tasks.iter().filter(|t| t.done).count();
+    // "status" (active first, the default), "name", "progress" (most done first) or
+    // "recent" (last edited first); ties fall back to name so the order is stable
+    let by_name = |a: &Project, b: &Project| a.name.to_lowercase().cmp(&b.name.to_lowercase());
+    match sort.as_deref().map(normalize).as_deref() {
+        Some("name") => projects.sort_by(by_name),
+        Some("progress") => projects.sort_by(|a, b| {
+            b.progress.total_cmp(&a.progress).then_with(|| by_name(a, b))
+        }),
+        Some("recent") => projects.sort_by(|a, b| {
+            b.modified.cmp(&a.modified).then_with(|| by_name(a, b))
+        }),
+        _ => projects.sort_by(|a, b| {
+            let a_active = a.status.to_lowercase().contains("active");
+            let b_active = b.status.to_lowercase().contains("active");
+            b_active.cmp(&a_active).then_with(|| by_name(a, b))
+        }),
+    }
     
-    // Generate ID from filename
-    let id = path.file_stem()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_default();
+    projects
+}
+
+// Unix seconds of the file's last modification, 0 if it can't be read
+fn file_modified_secs(path: &std::path::Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs())
+}
+
+// Every parsed project in the projects dir, in directory order
+fn load_projects() -> Vec<Project> {
+    load_projects_in(&projects_dir())
+}
+
+fn load_projects_in(projects_dir: &std::path::Path) -> Vec<Project> {
+    let mut projects = Vec::new();
     
-    Project {
-        id,
-        name,
-        status,
-        category,
-        description,
-        task_count,
-        tasks_done,
-        tasks,
+    if let Ok(entries) = fs::read_dir(projects_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map_or(false, |e| e == "md") {
+                if let Ok(content) = fs::read_to_string(&path) {
+                    let project = parse_project(&content, &path);
+                    projects.push(project);
+                }
+            }
+        }
+    }
+    
+    projects
+}
+
+#[derive(Serialize)]
+pub struct CategoryProgress {
+    category: String,
+    project_count: usize,
+    task_count: usize,
+    tasks_done: usize,
+    // Task-weighted, so big projects count for more than small ones
+    percent_complete: f32,
+}
+
+// Per-category project/task totals, most open work first
+#[tauri::command]
+fn get_category_progress() -> Vec<CategoryProgress> {
+    let mut categories: Vec<CategoryProgress> = Vec::new();
+
+    for project in load_projects() {
+        let category = project.category.to_lowercase();
+        let idx = match categories.iter().position(|c| c.category == category) {
+            Some(idx) => idx,
+            None => {
+                categories.push(CategoryProgress {
+                    category,
+                    project_count: 0,
+                    task_count: 0,
+                    tasks_done: 0,
+                    percent_complete: 0.0,
+                });
+                categories.len() - 1
+            }
+        };
+        let entry = &mut categories[idx];
+        entry.project_count += 1;
+        entry.task_count += project.task_count;
+        entry.tasks_done += project.tasks_done;
+    }
+
+    for c in categories.iter_mut() {
+        if c.task_count > 0 {
+            c.percent_complete = c.tasks_done as f32 / c.task_count as f32 * 100.0;
+        }
+    }
+    categories.sort_by_key(|c| std::cmp::Reverse(c.task_count - c.tasks_done));
+    categories
+}
+
+#[derive(Serialize)]
+pub struct TaskSummary {
+    total_tasks: usize,
+    done_tasks: usize,
+    overdue_tasks: usize,
+    // Lowercased category -> (done, total)
+    by_category: HashMap<String, (usize, usize)>,
+}
+
+// Task totals across every non-archived project, for the overview tile
+#[tauri::command]
+fn get_task_summary() -> TaskSummary {
+    let mut summary = TaskSummary {
+        total_tasks: 0,
+        done_tasks: 0,
+        overdue_tasks: 0,
+        by_category: HashMap::new(),
+    };
+
+    for project in load_projects() {
+        summary.total_tasks += project.task_count;
+        summary.done_tasks += project.tasks_done;
+        summary.overdue_tasks += project.tasks.iter().filter(|t| t.overdue).count();
+        let entry = summary.by_category.entry(project.category.to_lowercase()).or_insert((0, 0));
+        entry.0 += project.tasks_done;
+        entry.1 += project.task_count;
+    }
+
+    summary
+}
+
+// (project id, dependency id) edges for every project's depends_on
+#[tauri::command]
+fn get_project_graph() -> Vec<(String, String)> {
+    let mut edges: Vec<(String, String)> = load_projects()
+        .into_iter()
+        .flat_map(|p| {
+            let id = p.id;
+            p.depends_on.into_iter().map(move |dep| (id.clone(), dep))
+        })
+        .collect();
+    edges.sort();
+    edges
+}
+
+#[derive(Serialize)]
+pub struct PrioritizedTask {
+    project_id: String,
+    project_name: String,
+    // Index for toggle_task and friends
+    task_index: usize,
+    #[serde(flatten)]
+    task: Task,
+}
+
+// Every task across projects: open before done, then by priority (unmarked last), then by
+// due date (undated last)
+#[tauri::command]
+fn get_prioritized_tasks() -> Vec<PrioritizedTask> {
+    let mut tasks: Vec<PrioritizedTask> = load_projects()
+        .into_iter()
+        .flat_map(|p| {
+            let (project_id, project_name) = (p.id, p.name);
+            p.tasks.into_iter().enumerate().map(move |(task_index, task)| PrioritizedTask {
+                project_id: project_id.clone(),
+                project_name: project_name.clone(),
+                task_index,
+                task,
+            })
+        })
+        .collect();
+
+    tasks.sort_by_key(|t| {
+        (
+            t.task.done,
+            t.task.priority.unwrap_or(u8::MAX),
+            t.task.due.is_none(),
+            t.task.due,
+        )
+    });
+    tasks
+}
+
+#[derive(Serialize)]
+pub struct ProjectMatch {
+    id: String,
+    name: String,
+    // Indices (as used by toggle_task) of tasks whose text matched
+    task_indices: Vec<usize>,
+}
+
+// Case-insensitive search over project names, descriptions and task text
+#[tauri::command]
+fn search_projects(query: String) -> Vec<ProjectMatch> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<ProjectMatch> = load_projects()
+        .into_iter()
+        .filter_map(|p| {
+            let task_indices: Vec<usize> = p.tasks
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| t.text.to_lowercase().contains(&query))
+                .map(|(i, _)| i)
+                .collect();
+            let project_matched = p.name.to_lowercase().contains(&query)
+                || p.description_full.to_lowercase().contains(&query);
+            if !project_matched && task_indices.is_empty() {
+                return None;
+            }
+            Some(ProjectMatch { id: p.id, name: p.name, task_indices })
+        })
+        .collect();
+    matches.sort_by_key(|m| m.name.to_lowercase());
+    matches
+}
+
+// Groups of project ids whose names match once trimmed and lowercased
+#[tauri::command]
+fn find_duplicate_projects() -> Vec<Vec<String>> {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+
+    for project in load_projects() {
+        let key = project.name.trim().to_lowercase();
+        match groups.iter_mut().find(|(name, _)| *name == key) {
+            Some((_, ids)) => ids.push(project.id),
+            None => groups.push((key, vec![project.id])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(_, mut ids)| {
+            ids.sort();
+            ids
+        })
+        .collect()
+}
+
+// Live watcher on the projects dir; dropping it stops watching
+static PROJECTS_WATCHER: Mutex<Option<notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>>> =
+    Mutex::new(None);
+
+// Emit "projects-changed" (with the ids of the touched files) whenever a project .md is
+// created, modified or deleted. Safe to call repeatedly: only one watcher ever runs.
+#[tauri::command]
+fn watch_projects(app: tauri::AppHandle) -> Result<(), String> {
+    let mut watcher = PROJECTS_WATCHER.lock().unwrap();
+    if watcher.is_some() {
+        return Ok(());
+    }
+
+    let dir = projects_dir();
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create projects dir: {}", e))?;
+
+    let mut debouncer = notify_debouncer_mini::new_debouncer(
+        Duration::from_millis(300),
+        move |res: notify_debouncer_mini::DebounceEventResult| {
+            let Ok(events) = res else {
+                return;
+            };
+            let mut ids: Vec<String> = events
+                .iter()
+                .filter(|e| e.path.extension().is_some_and(|ext| ext == "md"))
+                .filter_map(|e| e.path.file_stem().map(|s| s.to_string_lossy().to_string()))
+                .collect();
+            ids.sort();
+            ids.dedup();
+            if !ids.is_empty() {
+                let _ = app.emit("projects-changed", ids);
+            }
+        },
+    )
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    debouncer
+        .watcher()
+        .watch(&dir, notify::RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch projects dir: {}", e))?;
+
+    *watcher = Some(debouncer);
+    Ok(())
+}
+
+// (incomplete, complete) across every project, scanning checkbox lines only
+#[tauri::command]
+fn get_task_counts() -> (usize, usize) {
+    let mut todo = 0;
+    let mut done = 0;
+
+    if let Ok(entries) = fs::read_dir(projects_dir()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "md") {
+                if let Ok(content) = fs::read_to_string(&path) {
+                    let lines: Vec<&str> = content.lines().collect();
+                    for i in task_line_indices(&lines) {
+                        let trimmed = lines[i].trim();
+                        if trimmed.starts_with("- [x]") || trimmed.starts_with("- [X]") {
+                            done += 1;
+                        } else {
+                            todo += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    (todo, done)
+}
+
+// Number of lines taken by a leading "---" front matter block (both fences included), 0
+// if there isn't one; the line-based counterpart of split_front_matter
+fn front_matter_lines<S: AsRef<str>>(lines: &[S]) -> usize {
+    if lines.first().map(|l| l.as_ref()) != Some("---") {
+        return 0;
+    }
+    let Some(close) = lines.iter().skip(1).position(|l| l.as_ref().trim_end() == "---") else {
+        return 0;
+    };
+    // Like split_front_matter, a block that isn't valid YAML is just part of the body
+    let yaml: Vec<&str> = lines[1..=close].iter().map(|l| l.as_ref()).collect();
+    if serde_yaml::from_str::<serde_yaml::Value>(&yaml.join("\n")).is_err() {
+        return 0;
+    }
+    close + 2
+}
+
+// Split a leading "---" YAML block from the markdown body. Files without one (or with
+// YAML that doesn't parse) come back unchanged with no front matter.
+fn split_front_matter(content: &str) -> (Option<serde_yaml::Value>, &str) {
+    let Some(rest) = content.strip_prefix("---\n").or_else(|| content.strip_prefix("---\r\n")) else {
+        return (None, content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let yaml = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            return match serde_yaml::from_str::<serde_yaml::Value>(yaml) {
+                Ok(value) => (Some(value), body),
+                Err(_) => (None, content),
+            };
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+// Scalar front matter value as a string (dates and numbers arrive as non-strings)
+fn yaml_string(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// Leading whitespace in columns, with a tab as 4
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn parse_project(content: &str, path: &PathBuf) -> Project {
+    // Tasks are counted over the whole file, the same way the task edits count them
+    let file_lines: Vec<&str> = content.lines().collect();
+    let (front, content) = split_front_matter(content);
+    let front = front.unwrap_or(serde_yaml::Value::Null);
+    let front_str = |key: &str| front.get(key).and_then(yaml_string);
+    let lines: Vec<&str> = content.lines().collect();
+    
+    // Get name from first H1 or filename
+    let name = lines.iter()
+        .find(|l| l.starts_with("# "))
+        .map(|l| l.trim_start_matches("# ").to_string())
+        .unwrap_or_else(|| {
+            path.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+    
+    // Get status from front matter or a "Status: X" line
+    let status = front_str("status")
+        .or_else(|| lines.iter()
+            .find(|l| l.to_lowercase().starts_with("status:"))
+            .map(|l| l.split(':').nth(1).unwrap_or("").trim().to_string()))
+        .unwrap_or_else(|| "Unknown".to_string());
+    
+    // Get category from front matter or a "Category: X" line
+    let category = front_str("category")
+        .or_else(|| lines.iter()
+            .find(|l| l.to_lowercase().starts_with("category:"))
+            .map(|l| l.split(':').nth(1).unwrap_or("").trim().to_string()))
+        .unwrap_or_else(|| "personal".to_string());
+    
+    // Priority from front matter or a "Priority: X" line; due only from front matter
+    let priority = front_str("priority")
+        .or_else(|| lines.iter()
+            .find(|l| l.to_lowercase().starts_with("priority:"))
+            .map(|l| l.split(':').nth(1).unwrap_or("").trim().to_string())
+            .filter(|p| !p.is_empty()));
+    let due = front_str("due");
+    
+    // Tags as a YAML list or a comma-separated string
+    let tags: Vec<String> = match front.get("tags") {
+        Some(serde_yaml::Value::Sequence(items)) => items.iter().filter_map(yaml_string).collect(),
+        Some(serde_yaml::Value::String(list)) => list
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    };
+    
+    // Get description from ## Description section or first paragraph
+    let description = extract_section(content, "Description")
+        .or_else(|| {
+            lines.iter()
+                .skip_while(|l| l.starts_with('#') || l.starts_with("Status:") || l.starts_with("Created:") || l.starts_with("Priority:") || l.is_empty())
+                .next()
+                .map(|s| s.to_string())
+        })
+        .unwrap_or_default();
+    let description_full = extract_section_full(content, "Description")
+        .unwrap_or_else(|| description.clone());
+    
+    // Extract tasks. Nesting comes from indentation relative to earlier tasks: a task
+    // indented deeper than the one above is its child. Tabs count as 4 columns, so mixed
+    // tab/space files nest the way they look in most editors.
+    let mut parent_indents: Vec<usize> = Vec::new();
+    let today = chrono::Local::now().date_naive();
+    let tasks: Vec<Task> = task_line_indices(&file_lines)
+        .map(|i| file_lines[i])
+        .map(|l| {
+            let indent = indent_width(l);
+            while parent_indents.last().is_some_and(|&p| p >= indent) {
+                parent_indents.pop();
+            }
+            let depth = parent_indents.len().min(u8::MAX as usize) as u8;
+            parent_indents.push(indent);
+
+            let trimmed = l.trim();
+            let done = trimmed.starts_with("- [x]") || trimmed.starts_with("- [X]");
+            let (text, due, priority, tags) = parse_task_text(trimmed
+                .trim_start_matches("- [x] ")
+                .trim_start_matches("- [X] ")
+                .trim_start_matches("- [ ] "));
+            let overdue = is_overdue(done, due, today);
+            Task { text, done, depth, due, overdue, priority, tags }
+        })
+        .collect();
+    
+    let task_count = tasks.len();
+    let tasks_done = tasks.iter().filter(|t| t.done).count();
+    let progress = if task_count > 0 { tasks_done as f32 / task_count as f32 * 100.0 } else { 0.0 };
+    let completed = task_count > 0 && tasks_done == task_count;
+    
+    // Generate ID from filename
+    let id = path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    
+    // Dependencies: comma-separated ids, optionally as [[wiki-links]] or file names
+    let depends_on: Vec<String> = lines.iter()
+        .filter_map(|l| {
+            let lower = l.to_lowercase();
+            if lower.starts_with("blocked by:") || lower.starts_with("depends on:") {
+                l.split_once(':').map(|(_, rest)| rest)
+            } else {
+                None
+            }
+        })
+        .flat_map(|rest| rest.split(','))
+        .map(|dep| {
+            let dep = dep.trim().trim_start_matches("[[").trim_end_matches("]]");
+            dep.strip_suffix(".md").unwrap_or(dep).trim().to_string()
+        })
+        .filter(|dep| !dep.is_empty())
+        .collect();
+    
+    Project {
+        id,
+        name,
+        status,
+        category,
+        description,
+        description_full,
+        task_count,
+        tasks_done,
+        progress,
+        completed,
+        tasks,
+        depends_on,
+        archived: path.parent().is_some_and(|dir| dir.ends_with("archive")),
+        priority,
+        due,
+        tags,
+        modified: file_modified_secs(path),
+    }
+}
+
+fn extract_section(content: &str, section: &str) -> Option<String> {
+    let header = format!("## {}", section);
+    let mut in_section = false;
+    let mut result = Vec::new();
+    
+    for line in content.lines() {
+        if line.starts_with(&header) {
+            in_section = true;
+            continue;
+        }
+        if in_section {
+            if line.starts_with("## ") {
+                break;
+            }
+            if !line.is_empty() && result.is_empty() {
+                result.push(line.to_string());
+                break; // Just get first line of description
+            }
+        }
+    }
+    
+    if result.is_empty() { None } else { Some(result.join(" ")) }
+}
+
+// Every line of a section up to the next "## " heading, newlines kept and surrounding
+// blank lines trimmed
+fn extract_section_full(content: &str, section: &str) -> Option<String> {
+    let header = format!("## {}", section);
+    let mut lines = content.lines().skip_while(|l| !l.starts_with(&header));
+    lines.next()?;
+
+    let mut result: Vec<&str> = lines
+        .take_while(|l| !l.starts_with("## "))
+        .skip_while(|l| l.trim().is_empty())
+        .collect();
+    while result.last().is_some_and(|l| l.trim().is_empty()) {
+        result.pop();
+    }
+
+    if result.is_empty() { None } else { Some(result.join("\n")) }
+}
+
+// ─── Workspace activity log ──────────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct ActivityEntry {
+    timestamp: String,
+    kind: String,
+    message: String,
+    source: String,
+}
+
+// activity.log / activity.jsonl in the workspace root, plus anything under workspace/logs
+fn activity_log_files() -> Vec<PathBuf> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    let workspace = PathBuf::from(&home).join(".openclaw/workspace");
+
+    let mut files: Vec<PathBuf> = ["activity.log", "activity.jsonl"]
+        .iter()
+        .map(|name| workspace.join(name))
+        .filter(|p| p.is_file())
+        .collect();
+    if let Ok(entries) = fs::read_dir(workspace.join("logs")) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "log" || e == "jsonl") {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+// RFC 3339, "YYYY-MM-DD HH:MM:SS" (local time) or unix seconds/millis
+fn parse_log_timestamp(value: &serde_json::Value) -> Option<chrono::DateTime<chrono::Utc>> {
+    use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+    if let Some(n) = value.as_i64() {
+        return if n > 1_000_000_000_000 {
+            DateTime::from_timestamp_millis(n)
+        } else {
+            DateTime::from_timestamp(n, 0)
+        };
+    }
+    let s = value.as_str()?.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+        .and_then(|naive| chrono::Local.from_local_datetime(&naive).single())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+// One log line → entry. JSON lines use common field names; plain lines may lead
+// with "[timestamp]" or "YYYY-MM-DD HH:MM:SS".
+fn parse_activity_line(line: &str, source: &str) -> Option<(Option<chrono::DateTime<chrono::Utc>>, ActivityEntry)> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(line) {
+        if json.is_object() {
+            let pick = |keys: &[&str]| -> serde_json::Value {
+                keys.iter()
+                    .map(|k| json[*k].clone())
+                    .find(|v| !v.is_null())
+                    .unwrap_or(serde_json::Value::Null)
+            };
+            let ts = pick(&["timestamp", "ts", "time", "date"]);
+            let parsed = parse_log_timestamp(&ts);
+            let message = pick(&["message", "msg", "text", "summary"]);
+            return Some((parsed, ActivityEntry {
+                timestamp: parsed.map(|t| t.to_rfc3339()).unwrap_or_else(|| ts.as_str().unwrap_or("").to_string()),
+                kind: pick(&["type", "event", "kind", "level"]).as_str().unwrap_or("").to_string(),
+                message: message.as_str().map(|s| s.to_string()).unwrap_or_else(|| json.to_string()),
+                source: source.to_string(),
+            }));
+        }
+    }
+
+    let (raw_ts, message) = if let Some(rest) = line.strip_prefix('[') {
+        match rest.split_once(']') {
+            Some((ts, msg)) => (ts.to_string(), msg.trim()),
+            None => (String::new(), line),
+        }
+    } else {
+        let mut parts = line.splitn(3, ' ');
+        let date = parts.next().unwrap_or("");
+        let time = parts.next().unwrap_or("");
+        let rest = parts.next().unwrap_or("");
+        if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok() && time.contains(':') {
+            (format!("{} {}", date, time), rest.trim())
+        } else if date.len() > 10 && parse_log_timestamp(&serde_json::json!(date)).is_some() {
+            (date.to_string(), line[date.len()..].trim())
+        } else {
+            (String::new(), line)
+        }
+    };
+    let parsed = parse_log_timestamp(&serde_json::json!(raw_ts));
+    Some((parsed, ActivityEntry {
+        timestamp: parsed.map(|t| t.to_rfc3339()).unwrap_or(raw_ts),
+        kind: String::new(),
+        message: message.to_string(),
+        source: source.to_string(),
+    }))
+}
+
+#[tauri::command]
+fn get_recent_activity(limit: usize) -> Result<Vec<ActivityEntry>, String> {
+    let mut entries = Vec::new();
+    for path in activity_log_files() {
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+        let source = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        entries.extend(content.lines().filter_map(|l| parse_activity_line(l, &source)));
+    }
+
+    // Newest first; undated lines keep file order and sink below dated ones
+    entries.reverse();
+    entries.sort_by_key(|(ts, _)| std::cmp::Reverse(*ts));
+    entries.truncate(limit);
+
+    Ok(entries.into_iter().map(|(_, entry)| entry).collect())
+}
+
+#[derive(Serialize)]
+pub struct GatewayConfig {
+    token: String,
+    port: u16,
+}
+
+fn openclaw_config_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_default();
+    PathBuf::from(&home).join(".openclaw/openclaw.json")
+}
+
+// dashboard.<key> from openclaw.json, or Null when the file or key is absent
+fn dashboard_setting(key: &str) -> serde_json::Value {
+    fs::read_to_string(openclaw_config_path())
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .map(|json| json["dashboard"][key].clone())
+        .unwrap_or(serde_json::Value::Null)
+}
+
+// Open a file in dashboard.editor if configured, otherwise the platform's default text editor
+fn open_in_editor(path: &std::path::Path) -> Result<(), String> {
+    let mut cmd = match dashboard_setting("editor").as_str() {
+        Some(editor) if !editor.trim().is_empty() => Command::new(editor.trim()),
+        _ if cfg!(target_os = "macos") => {
+            let mut c = Command::new("open");
+            c.arg("-t");
+            c
+        }
+        _ if cfg!(target_os = "windows") => {
+            let mut c = Command::new("cmd");
+            c.args(["/C", "start", ""]);
+            c
+        }
+        _ => Command::new("xdg-open"),
+    };
+    cmd.arg(path)
+        .spawn()
+        .map_err(|e| format!("Failed to open editor: {}", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+fn edit_config() -> Result<(), String> {
+    let config_path = openclaw_config_path();
+    if !config_path.exists() {
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create config dir: {}", e))?;
+        }
+        let scaffold = serde_json::json!({
+            "gateway": { "port": 18789, "auth": { "token": "" } },
+            "dashboard": {},
+        });
+        let content = serde_json::to_string_pretty(&scaffold).unwrap();
+        fs::write(&config_path, content + "\n")
+            .map_err(|e| format!("Failed to write openclaw.json: {}", e))?;
+    }
+    open_in_editor(&config_path)
+}
+
+#[tauri::command]
+fn get_gateway_config() -> Result<GatewayConfig, String> {
+    let home = std::env::var("HOME").map_err(|_| "HOME not set".to_string())?;
+    let config_path = PathBuf::from(&home).join(".openclaw/openclaw.json");
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read openclaw.json: {}", e))?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse openclaw.json: {}", e))?;
+    
+    let token = json["gateway"]["auth"]["token"]
+        .as_str()
+        .ok_or("gateway.auth.token not found in config")?
+        .to_string();
+    let port = json["gateway"]["port"]
+        .as_u64()
+        .ok_or("gateway.port not found in config")? as u16;
+    
+    Ok(GatewayConfig { token, port })
+}
+
+// The whole parsed openclaw.json
+#[tauri::command]
+fn get_openclaw_config() -> Result<serde_json::Value, String> {
+    let config_path = openclaw_config_path();
+    let content = fs::read_to_string(&config_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("openclaw.json not found at {}", config_path.display()),
+        _ => format!("Failed to read openclaw.json: {}", e),
+    })?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("openclaw.json is malformed: {}", e))
+}
+
+// Update gateway.auth.token and gateway.port, leaving every other key as it was
+#[tauri::command]
+fn set_gateway_config(token: String, port: u16) -> Result<(), String> {
+    if port == 0 {
+        return Err("Port must be between 1 and 65535".to_string());
+    }
+
+    let config_path = openclaw_config_path();
+    let mut json: serde_json::Value = match fs::read_to_string(&config_path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse openclaw.json: {}", e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
+        Err(e) => return Err(format!("Failed to read openclaw.json: {}", e)),
+    };
+    if !json.is_object() {
+        return Err("openclaw.json is not a JSON object".to_string());
+    }
+
+    // Indexing only inserts into objects, so replace anything else along the path
+    if !json["gateway"].is_object() {
+        json["gateway"] = serde_json::json!({});
+    }
+    if !json["gateway"]["auth"].is_object() {
+        json["gateway"]["auth"] = serde_json::json!({});
+    }
+    json["gateway"]["auth"]["token"] = serde_json::Value::String(token);
+    json["gateway"]["port"] = serde_json::json!(port);
+
+    // Two-space pretty printing with key order preserved, matching the hand-edited file
+    let content = serde_json::to_string_pretty(&json)
+        .map_err(|e| format!("JSON serialization error: {}", e))?;
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    write_atomic(&config_path, &(content + "\n"))
+        .map_err(|e| format!("Failed to write openclaw.json: {}", e))
+}
+
+// ─── Locale ──────────────────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct LocaleInfo {
+    // BCP 47 style, e.g. "en-US"
+    locale: String,
+    // IANA zone, e.g. "America/New_York"
+    timezone: String,
+    // ISO 4217, e.g. "USD"
+    currency: String,
+}
+
+// "en_US.UTF-8" / "en_US@euro" / "en_US" → "en-US"; None for C/POSIX
+fn normalize_locale(raw: &str) -> Option<String> {
+    let base = raw.split(['.', '@']).next()?.trim();
+    if base.is_empty() || base == "C" || base == "POSIX" {
+        return None;
+    }
+    Some(base.replace('_', "-"))
+}
+
+fn detect_locale() -> Option<String> {
+    for var in ["LC_ALL", "LC_MONETARY", "LANG"] {
+        if let Some(locale) = std::env::var(var).ok().as_deref().and_then(normalize_locale) {
+            return Some(locale);
+        }
+    }
+    // GUI apps on macOS usually don't inherit LANG; ask the system preference instead
+    if cfg!(target_os = "macos") {
+        let output = Command::new("defaults").args(["read", "-g", "AppleLocale"]).output().ok()?;
+        if output.status.success() {
+            return normalize_locale(String::from_utf8_lossy(&output.stdout).trim());
+        }
+    }
+    None
+}
+
+fn detect_timezone() -> Option<String> {
+    if let Ok(tz) = std::env::var("TZ") {
+        let tz = tz.trim_start_matches(':').trim();
+        if !tz.is_empty() {
+            return Some(tz.to_string());
+        }
+    }
+    // /etc/localtime links into the zoneinfo database on macOS and most Linux distros
+    if let Ok(target) = fs::read_link("/etc/localtime") {
+        let target = target.to_string_lossy().to_string();
+        if let Some((_, zone)) = target.split_once("zoneinfo/") {
+            return Some(zone.to_string());
+        }
+    }
+    fs::read_to_string("/etc/timezone")
+        .ok()
+        .map(|tz| tz.trim().to_string())
+        .filter(|tz| !tz.is_empty())
+}
+
+// Currency for the region part of a locale; only the common cases
+fn currency_for_locale(locale: &str) -> Option<&'static str> {
+    let region = locale.rsplit('-').next()?;
+    let currency = match region {
+        "US" => "USD",
+        "CA" => "CAD",
+        "GB" => "GBP",
+        "AU" => "AUD",
+        "NZ" => "NZD",
+        "JP" => "JPY",
+        "CN" => "CNY",
+        "IN" => "INR",
+        "CH" => "CHF",
+        "SE" => "SEK",
+        "NO" => "NOK",
+        "DK" => "DKK",
+        "MX" => "MXN",
+        "BR" => "BRL",
+        "DE" | "FR" | "ES" | "IT" | "NL" | "BE" | "AT" | "IE" | "PT" | "FI" | "GR" => "EUR",
+        _ => return None,
+    };
+    Some(currency)
+}
+
+// System locale, timezone and currency; dashboard.locale / .timezone / .currency override
+// each field, and anything undetected falls back to en-US / UTC / USD
+#[tauri::command]
+fn get_locale_info() -> LocaleInfo {
+    let setting = |key: &str| dashboard_setting(key).as_str().map(|v| v.to_string());
+
+    let locale = setting("locale")
+        .or_else(detect_locale)
+        .unwrap_or_else(|| "en-US".to_string());
+    let timezone = setting("timezone")
+        .or_else(detect_timezone)
+        .unwrap_or_else(|| "UTC".to_string());
+    let currency = setting("currency")
+        .or_else(|| currency_for_locale(&locale).map(|c| c.to_string()))
+        .unwrap_or_else(|| "USD".to_string());
+
+    LocaleInfo {
+        locale,
+        timezone,
+        currency,
+    }
+}
+
+// ─── Gateway latency ─────────────────────────────────────────────────────────
+
+const GATEWAY_LATENCY_LEN: usize = 60;
+const DEFAULT_GATEWAY_PING_SECS: u64 = 10;
+const DEFAULT_GATEWAY_PORT: u16 = 18789;
+
+// Round-trip ms per ping, oldest first; None for a failed ping
+static GATEWAY_LATENCY: Mutex<VecDeque<Option<u64>>> = Mutex::new(VecDeque::new());
+static GATEWAY_PINGER: Mutex<Option<tauri::async_runtime::JoinHandle<()>>> = Mutex::new(None);
+
+// Re-read each ping so a port change in openclaw.json is picked up without a restart
+fn gateway_port() -> u16 {
+    fs::read_to_string(openclaw_config_path())
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|json| json["gateway"]["port"].as_u64())
+        .map(|p| p as u16)
+        .unwrap_or(DEFAULT_GATEWAY_PORT)
+}
+
+async fn ping_gateway(client: &reqwest::Client) -> Option<u64> {
+    let url = format!("http://127.0.0.1:{}/health", gateway_port());
+    let started = Instant::now();
+    match client.get(&url).send().await {
+        Ok(resp) if resp.status().is_success() => Some(started.elapsed().as_millis() as u64),
+        _ => None,
+    }
+}
+
+// Ping the gateway every interval_secs (replacing any pinger already running)
+#[tauri::command]
+fn start_gateway_ping(interval_secs: Option<u64>) {
+    let interval = Duration::from_secs(interval_secs.unwrap_or(DEFAULT_GATEWAY_PING_SECS).max(1));
+    let handle = tauri::async_runtime::spawn(async move {
+        let client = reqwest::Client::builder()
+            .timeout(interval.min(Duration::from_secs(5)))
+            .build()
+            .unwrap_or_default();
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let sample = ping_gateway(&client).await;
+            let mut history = GATEWAY_LATENCY.lock().unwrap();
+            if history.len() == GATEWAY_LATENCY_LEN {
+                history.pop_front();
+            }
+            history.push_back(sample);
+        }
+    });
+    if let Some(previous) = GATEWAY_PINGER.lock().unwrap().replace(handle) {
+        previous.abort();
+    }
+}
+
+#[tauri::command]
+fn stop_gateway_ping() {
+    if let Some(handle) = GATEWAY_PINGER.lock().unwrap().take() {
+        handle.abort();
+    }
+}
+
+#[tauri::command]
+fn get_gateway_latency_history() -> Vec<Option<u64>> {
+    GATEWAY_LATENCY.lock().unwrap().iter().copied().collect()
+}
+
+const GATEWAY_CHECK_TIMEOUT_SECS: u64 = 3;
+
+#[derive(Serialize)]
+pub struct GatewayStatus {
+    reachable: bool,
+    authorized: bool,
+}
+
+// One request to the same /health route ping_gateway uses, carrying the gateway token.
+// An unreachable gateway fails fast rather than erroring. Authorized only on a 2xx, so a
+// 401/403 (token rejected), 404 or 5xx never passes for an accepted token. A gateway that
+// serves /health without checking the token reports authorized whenever it's up.
+#[tauri::command]
+async fn check_gateway() -> Result<GatewayStatus, String> {
+    let config = get_gateway_config()?;
+    let url = format!("http://127.0.0.1:{}/health", config.port);
+    let response = http_client()
+        .get(&url)
+        .bearer_auth(&config.token)
+        .timeout(Duration::from_secs(GATEWAY_CHECK_TIMEOUT_SECS))
+        .send()
+        .await;
+    let status = match response {
+        Ok(resp) => GatewayStatus { reachable: true, authorized: resp.status().is_success() },
+        Err(_) => GatewayStatus { reachable: false, authorized: false },
+    };
+    Ok(status)
+}
+
+// ─── Workspace backup / restore ──────────────────────────────────────────────
+
+// Top-level folders inside a backup archive
+const BACKUP_PROJECTS_DIR: &str = "projects";
+const BACKUP_FINANCE_DIR: &str = "finance-dashboard";
+
+// Temp, editor swap and cache files aren't worth carrying around
+fn skip_in_backup(name: &str) -> bool {
+    name.starts_with('.')
+        || name.ends_with('~')
+        || name.ends_with(".tmp")
+        || name.ends_with(".swp")
+        || name.eq_ignore_ascii_case("cache")
+        || name == "node_modules"
+}
+
+// (file on disk, path inside the archive) for every file under dir
+fn collect_backup_files(dir: &std::path::Path, prefix: &str, out: &mut Vec<(PathBuf, String)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if skip_in_backup(&name) {
+            continue;
+        }
+        let path = entry.path();
+        let archive_name = format!("{}/{}", prefix, name);
+        if path.is_dir() {
+            collect_backup_files(&path, &archive_name, out);
+        } else if path.is_file() {
+            out.push((path, archive_name));
+        }
+    }
+}
+
+// Zip the projects dir (plus the finance config if asked) into a timestamped archive in dest
+#[tauri::command]
+fn backup_workspace(dest: String, include_finance: Option<bool>) -> Result<String, String> {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    let mut files = Vec::new();
+    collect_backup_files(&projects_dir(), BACKUP_PROJECTS_DIR, &mut files);
+    if include_finance.unwrap_or(false) {
+        collect_backup_files(&finance_dir(), BACKUP_FINANCE_DIR, &mut files);
+    }
+    if files.is_empty() {
+        return Err("Nothing to back up".to_string());
+    }
+
+    let dest_dir = PathBuf::from(&dest);
+    fs::create_dir_all(&dest_dir)
+        .map_err(|e| format!("Failed to create backup dir: {}", e))?;
+    let archive_path = dest_dir.join(format!(
+        "openclaw-backup-{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    let file = fs::File::create(&archive_path)
+        .map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for (path, name) in &files {
+        let data = fs::read(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("Failed to add {}: {}", name, e))?;
+        zip.write_all(&data)
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+
+    Ok(archive_path.to_string_lossy().to_string())
+}
+
+// Unpack a backup_workspace archive into place. Refuses to overwrite existing files
+// unless force is set; returns the number of files restored.
+#[tauri::command]
+fn restore_workspace(archive: String, force: Option<bool>) -> Result<usize, String> {
+    let file = fs::File::open(&archive)
+        .map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut zip = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    // Map each entry to its destination first so a conflict aborts before anything is written
+    let mut targets: Vec<(usize, PathBuf)> = Vec::new();
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i)
+            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        // enclosed_name rejects absolute paths and ".." components
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        let target = if let Ok(rest) = name.strip_prefix(BACKUP_PROJECTS_DIR) {
+            projects_dir().join(rest)
+        } else if let Ok(rest) = name.strip_prefix(BACKUP_FINANCE_DIR) {
+            finance_dir().join(rest)
+        } else {
+            continue;
+        };
+        targets.push((i, target));
+    }
+
+    if !force.unwrap_or(false) {
+        let existing: Vec<String> = targets
+            .iter()
+            .filter(|(_, target)| target.exists())
+            .map(|(_, target)| target.display().to_string())
+            .collect();
+        if !existing.is_empty() {
+            return Err(format!(
+                "Restore would overwrite {} existing file(s), e.g. {}",
+                existing.len(),
+                existing[0]
+            ));
+        }
+    }
+
+    for (i, target) in &targets {
+        let mut entry = zip.by_index(*i)
+            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let mut out = fs::File::create(target)
+            .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("Failed to extract {}: {}", target.display(), e))?;
+    }
+
+    Ok(targets.len())
+}
+
+// ─── Shared HTTP client ──────────────────────────────────────────────────────
+
+// Per-request timeout for Yahoo, Coinbase and SnapTrade calls
+const HTTP_TIMEOUT_SECS: u64 = 10;
+
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+// One client for the whole app so connections are pooled; every request times out
+// after HTTP_TIMEOUT_SECS instead of hanging the command on a dead endpoint
+fn http_client() -> &'static reqwest::Client {
+    HTTP_CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+// Error text for a failed request, with timeouts spelled out rather than reqwest's chain
+fn request_error(e: reqwest::Error) -> String {
+    if e.is_timeout() {
+        format!("timed out after {}s", HTTP_TIMEOUT_SECS)
+    } else {
+        e.to_string()
+    }
+}
+
+// ─── Yahoo Finance chart requests ────────────────────────────────────────────
+
+// Default cap on a chart response; override with dashboard.yahooMaxBodyBytes
+const YAHOO_MAX_BODY_BYTES: u64 = 5 * 1024 * 1024;
+
+// Stream a response body, giving up (None) as soon as it grows past max_bytes
+async fn read_body_capped(mut resp: reqwest::Response, max_bytes: u64) -> Result<Option<Vec<u8>>, String> {
+    if resp.content_length().is_some_and(|len| len > max_bytes) {
+        return Ok(None);
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = resp.chunk().await.map_err(|e| format!("read error: {}", request_error(e)))? {
+        if (body.len() + chunk.len()) as u64 > max_bytes {
+            return Ok(None);
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(Some(body))
+}
+
+// Base delay before the first retry; doubles on each further attempt
+const YAHOO_RETRY_BASE_MS: u64 = 500;
+
+// Exponential backoff with up to one base delay of jitter, so concurrent
+// symbols that failed together don't retry in lockstep
+fn yahoo_retry_delay(attempt: u32) -> Duration {
+    let jitter = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64 % YAHOO_RETRY_BASE_MS)
+        .unwrap_or(0);
+    Duration::from_millis(YAHOO_RETRY_BASE_MS * 2u64.pow(attempt.saturating_sub(1)) + jitter)
+}
+
+// Chart JSON for a symbol. Network errors, 429 and 5xx are retried up to `attempts`
+// tries in total; any other response (including Yahoo's 404 "not found" body) is parsed.
+async fn fetch_yahoo_chart(
+    client: &reqwest::Client,
+    symbol: &str,
+    range: &str,
+    interval: &str,
+    attempts: u32,
+) -> Result<serde_json::Value, String> {
+    // Symbols like "^GSPC", "SI=F" or "BRK/B" need encoding as a single path segment
+    let mut url = reqwest::Url::parse("https://query2.finance.yahoo.com/v8/finance/chart")
+        .map_err(|e| format!("Invalid chart URL: {}", e))?;
+    url.path_segments_mut()
+        .map_err(|_| "Invalid chart URL".to_string())?
+        .push(symbol);
+    url.query_pairs_mut()
+        .append_pair("interval", interval)
+        .append_pair("range", range);
+    let mut attempt = 1;
+    let resp = loop {
+        let error = match client.get(url.clone()).header("User-Agent", "Mozilla/5.0").send().await {
+            Ok(resp) => {
+                let status = resp.status();
+                if status != reqwest::StatusCode::TOO_MANY_REQUESTS && !status.is_server_error() {
+                    break resp;
+                }
+                format!("{} HTTP {}", symbol, status.as_u16())
+            }
+            Err(e) => format!("{} fetch error: {}", symbol, request_error(e)),
+        };
+        if attempt >= attempts {
+            return Err(error);
+        }
+        tokio::time::sleep(yahoo_retry_delay(attempt)).await;
+        attempt += 1;
+    };
+
+    let limit = dashboard_setting("yahooMaxBodyBytes").as_u64().unwrap_or(YAHOO_MAX_BODY_BYTES);
+    let body = read_body_capped(resp, limit)
+        .await
+        .map_err(|e| format!("{} {}", symbol, e))?
+        .ok_or_else(|| format!(
+            "{} response for range={} interval={} exceeds {} bytes; try a shorter range or a coarser interval",
+            symbol, range, interval, limit
+        ))?;
+
+    serde_json::from_slice(&body).map_err(|e| format!("{} json parse error: {}", symbol, e))
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SymbolMeta {
+    symbol: String,
+    name: String,
+    exchange: String,
+    currency: Option<String>,
+}
+
+fn symbol_meta_cache_path() -> PathBuf {
+    finance_dir().join("symbol-meta.json")
+}
+
+fn read_symbol_meta_cache() -> std::collections::HashMap<String, SymbolMeta> {
+    fs::read_to_string(symbol_meta_cache_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+type KeyLocks = OnceLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>;
+
+// One async lock per key (e.g. a ticker symbol), so concurrent callers for the same key
+// wait for the first fetch and then find it cached instead of all going to the network
+fn key_lock(locks: &'static KeyLocks, key: &str) -> Arc<tokio::sync::Mutex<()>> {
+    locks
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap()
+        .entry(key.to_string())
+        .or_default()
+        .clone()
+}
+
+static SYMBOL_META_FETCHES: KeyLocks = OnceLock::new();
+// Serializes the read-modify-write of symbol-meta.json so lookups for different symbols
+// don't drop each other's entries
+static SYMBOL_META_WRITE: Mutex<()> = Mutex::new(());
+
+// Company name and exchange for a ticker. Names don't change, so once looked up they're
+// served from finance_dir/symbol-meta.json without touching the network.
+// This reads the chart endpoint's meta rather than v7/finance/quote: the quote endpoint
+// now rejects requests without a cookie + crumb session, and the chart meta carries the
+// same longName / exchange fields without one.
+#[tauri::command]
+async fn fetch_symbol_meta(symbol: String) -> Result<SymbolMeta, String> {
+    let symbol = symbol.trim().to_uppercase();
+    if symbol.is_empty() {
+        return Err("Symbol is empty".to_string());
+    }
+
+    let lock = key_lock(&SYMBOL_META_FETCHES, &symbol);
+    let _fetching = lock.lock().await;
+    if let Some(meta) = read_symbol_meta_cache().get(&symbol) {
+        return Ok(meta.clone());
+    }
+
+    let client = http_client();
+    let data = fetch_yahoo_chart(client, &symbol, "1d", "1d", 1).await?;
+    let chart = &data["chart"];
+    if !chart["error"].is_null() || chart["result"][0].is_null() {
+        return Err(format!("Symbol not found: {}", symbol));
+    }
+
+    let meta = &chart["result"][0]["meta"];
+    let name = meta["longName"]
+        .as_str()
+        .or_else(|| meta["shortName"].as_str())
+        .unwrap_or(&symbol)
+        .to_string();
+    let exchange = meta["fullExchangeName"]
+        .as_str()
+        .or_else(|| meta["exchangeName"].as_str())
+        .unwrap_or("")
+        .to_string();
+    let result = SymbolMeta {
+        symbol: symbol.clone(),
+        name,
+        exchange,
+        currency: meta["currency"].as_str().map(|c| c.to_string()),
+    };
+
+    // A failed cache write just means we look it up again next time
+    let _writing = SYMBOL_META_WRITE.lock().unwrap();
+    let mut cache = read_symbol_meta_cache();
+    cache.insert(symbol, result.clone());
+    if fs::create_dir_all(finance_dir()).is_ok() {
+        if let Ok(json) = serde_json::to_string_pretty(&cache) {
+            let _ = fs::write(symbol_meta_cache_path(), json);
+        }
+    }
+
+    Ok(result)
+}
+
+#[derive(Serialize, Clone)]
+pub struct TickerData {
+    symbol: String,
+    label: String,
+    // Display string from format_price; price_raw is the unformatted quote
+    price: String,
+    price_raw: f64,
+    change: f64,
+    // "up" / "down" / "flat"; see ticker_trend
+    trend: String,
+}
+
+// Percent moves smaller than this read as "flat"; override with dashboard.tickerFlatEpsilon
+const DEFAULT_TICKER_FLAT_EPSILON: f64 = 0.05;
+
+fn ticker_trend(change: f64, epsilon: f64) -> String {
+    if change.abs() < epsilon {
+        "flat".to_string()
+    } else if change > 0.0 {
+        "up".to_string()
+    } else {
+        "down".to_string()
     }
 }
 
-fn extract_section(content: &str, section: &str) -> Option<String> {
-    let header = format!("## {}", section);
-    let mut in_section = false;
-    let mut result = Vec::new();
-    
-    for line in content.lines() {
-        if line.starts_with(&header) {
-            in_section = true;
-            continue;
+#[derive(Deserialize, Clone)]
+pub struct TickerSpec {
+    // Yahoo Finance symbol, e.g. "BTC-USD" or "SI=F"
+    symbol: String,
+    label: String,
+    icon: String,
+}
+
+// dashboard.tickers from openclaw.json if set, else BTC / TSLA / Silver
+fn default_ticker_specs() -> Vec<TickerSpec> {
+    if let Ok(specs) = serde_json::from_value::<Vec<TickerSpec>>(dashboard_setting("tickers")) {
+        if !specs.is_empty() {
+            return specs;
         }
-        if in_section {
-            if line.starts_with("## ") {
-                break;
-            }
-            if !line.is_empty() && result.is_empty() {
-                result.push(line.to_string());
-                break; // Just get first line of description
+    }
+    // Icons as escapes so a re-encoded source file can't turn them into mojibake
+    [("BTC-USD", "BTC", "\u{20BF}"), ("TSLA", "TSLA", "\u{26A1}"), ("SI=F", "Silver", "\u{1FA99}")]
+        .iter()
+        .map(|(symbol, label, icon)| TickerSpec {
+            symbol: symbol.to_string(),
+            label: label.to_string(),
+            icon: icon.to_string(),
+        })
+        .collect()
+}
+
+// "$1,234" from 1000 up (whole dollars), "$12.34" below
+fn format_price(price: f64) -> String {
+    if price >= 1000.0 {
+        let digits = (price as i64).to_string();
+        let mut grouped = String::new();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push(',');
             }
+            grouped.push(c);
         }
+        format!("${}", grouped)
+    } else {
+        format!("${:.2}", price)
     }
-    
-    if result.is_empty() { None } else { Some(result.join(" ")) }
 }
 
+// Latest quote for one ticker, with percent change from the previous close
+async fn fetch_ticker(
+    client: &reqwest::Client,
+    spec: &TickerSpec,
+    epsilon: f64,
+    attempts: u32,
+) -> Result<TickerData, String> {
+    let data = fetch_yahoo_chart(client, &spec.symbol, "2d", "1d", attempts).await?;
+    let meta = data["chart"]["result"][0]["meta"]
+        .as_object()
+        .ok_or_else(|| format!("{}: meta not found", spec.symbol))?;
+
+    let price = meta.get("regularMarketPrice").and_then(|v| v.as_f64()).unwrap_or(0.0);
+    if price <= 0.0 {
+        return Err(format!("{}: no price", spec.symbol));
+    }
+    let prev = meta.get("chartPreviousClose").and_then(|v| v.as_f64())
+        .or_else(|| meta.get("previousClose").and_then(|v| v.as_f64())).unwrap_or(0.0);
+    let change = if prev > 0.0 { ((price - prev) / prev) * 100.0 } else { 0.0 };
+
+    Ok(TickerData {
+        symbol: spec.icon.clone(),
+        label: spec.label.clone(),
+        price: format_price(price),
+        price_raw: price,
+        change,
+        trend: ticker_trend(change, epsilon),
+    })
+}
+
+// One entry per requested ticker: the quote, or why it couldn't be fetched
 #[derive(Serialize)]
-pub struct GatewayConfig {
-    token: String,
-    port: u16,
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum TickerResult {
+    Ok(TickerData),
+    Error {
+        symbol: String,
+        label: String,
+        icon: String,
+        error: String,
+    },
 }
 
+// Quotes for the given tickers in order; None or an empty list means the configured defaults.
+// Quotes younger than the cache TTL are reused unless force is set. attempts is the
+// number of tries per symbol (default 3; 1 disables retrying).
 #[tauri::command]
-fn get_gateway_config() -> Result<GatewayConfig, String> {
-    let home = std::env::var("HOME").map_err(|_| "HOME not set".to_string())?;
-    let config_path = PathBuf::from(&home).join(".openclaw/openclaw.json");
-    let content = fs::read_to_string(&config_path)
-        .map_err(|e| format!("Failed to read openclaw.json: {}", e))?;
-    let json: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse openclaw.json: {}", e))?;
-    
-    let token = json["gateway"]["auth"]["token"]
-        .as_str()
-        .ok_or("gateway.auth.token not found in config")?
-        .to_string();
-    let port = json["gateway"]["port"]
-        .as_u64()
-        .ok_or("gateway.port not found in config")? as u16;
-    
-    Ok(GatewayConfig { token, port })
+async fn fetch_tickers(
+    symbols: Option<Vec<TickerSpec>>,
+    force: Option<bool>,
+    attempts: Option<u32>,
+) -> Vec<TickerResult> {
+    let specs = symbols.filter(|s| !s.is_empty()).unwrap_or_else(default_ticker_specs);
+    let attempts = attempts.unwrap_or(DEFAULT_TICKER_FETCH_ATTEMPTS).max(1);
+    load_tickers(&specs, force.unwrap_or(false), attempts).await
 }
 
+// Tries per symbol before a ticker is reported as failed
+const DEFAULT_TICKER_FETCH_ATTEMPTS: u32 = 3;
 
-#[derive(Serialize)]
-pub struct TickerData {
-    symbol: String,
-    label: String,
-    price: String,
-    change: f64,
+// Default lifetime of a cached quote; override with dashboard.tickerCacheSecs (0 disables)
+const DEFAULT_TICKER_CACHE_SECS: u64 = 30;
+
+// Last successful quote per Yahoo symbol, so panels mounting together share one fetch
+static TICKER_CACHE: OnceLock<Mutex<HashMap<String, (Instant, TickerData)>>> = OnceLock::new();
+
+fn ticker_cache() -> &'static Mutex<HashMap<String, (Instant, TickerData)>> {
+    TICKER_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-#[tauri::command]
-async fn fetch_tickers() -> Vec<TickerData> {
-    let mut results = Vec::new();
-    let client = reqwest::Client::new();
-
-    // Bitcoin from Yahoo Finance (BTC-USD)
-    match client.get("https://query2.finance.yahoo.com/v8/finance/chart/BTC-USD?interval=1d&range=2d")
-        .header("User-Agent", "Mozilla/5.0")
-        .send().await {
-        Ok(resp) => {
-            match resp.json::<serde_json::Value>().await {
-                Ok(data) => {
-                    eprintln!("BTC data keys: {:?}", data["chart"]["result"][0]["meta"].as_object().map(|m| m.keys().collect::<Vec<_>>()));
-                    if let Some(meta) = data["chart"]["result"][0]["meta"].as_object() {
-                        let price = meta.get("regularMarketPrice").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                        let prev = meta.get("chartPreviousClose").and_then(|v| v.as_f64())
-                            .or_else(|| meta.get("previousClose").and_then(|v| v.as_f64())).unwrap_or(0.0);
-                        let change = if prev > 0.0 { ((price - prev) / prev) * 100.0 } else { 0.0 };
-                        eprintln!("BTC price: {}, prev: {}, change: {}", price, prev, change);
-                        let p = price as i64;
-                        let formatted = if p >= 1000 {
-                            format!("${},{:03}", p / 1000, p % 1000)
-                        } else {
-                            format!("${}", p)
-                        };
-                        results.push(TickerData {
-                            symbol: "₿".into(),
-                            label: "BTC".into(),
-                            price: formatted,
-                            change,
-                        });
-                    } else {
-                        eprintln!("BTC: meta not found");
-                    }
-                }
-                Err(e) => eprintln!("BTC json parse error: {}", e),
-            }
-        }
-        Err(e) => eprintln!("BTC fetch error: {}", e),
-    }
-
-    // TSLA from Yahoo Finance
-    if let Ok(resp) = client.get("https://query2.finance.yahoo.com/v8/finance/chart/TSLA?interval=1d&range=2d")
-        .header("User-Agent", "Mozilla/5.0")
-        .send().await {
-        if let Ok(data) = resp.json::<serde_json::Value>().await {
-            if let Some(meta) = data["chart"]["result"][0]["meta"].as_object() {
-                let price = meta.get("regularMarketPrice").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                let prev = meta.get("chartPreviousClose").and_then(|v| v.as_f64())
-                    .or_else(|| meta.get("previousClose").and_then(|v| v.as_f64())).unwrap_or(0.0);
-                let change = if prev > 0.0 { ((price - prev) / prev) * 100.0 } else { 0.0 };
-                if price > 0.0 {
-                    results.push(TickerData {
-                        symbol: "⚡".into(),
-                        label: "TSLA".into(),
-                        price: format!("${:.2}", price),
-                        change,
-                    });
-                }
-            }
+static TICKER_FETCHES: KeyLocks = OnceLock::new();
+
+// Latest quotes for the given tickers, fetched concurrently and returned in input order
+async fn load_tickers(specs: &[TickerSpec], force: bool, attempts: u32) -> Vec<TickerResult> {
+    let client = http_client();
+    let epsilon = dashboard_setting("tickerFlatEpsilon").as_f64().unwrap_or(DEFAULT_TICKER_FLAT_EPSILON);
+    let ttl = Duration::from_secs(
+        dashboard_setting("tickerCacheSecs").as_u64().unwrap_or(DEFAULT_TICKER_CACHE_SECS),
+    );
+
+    let results = futures::future::join_all(specs.iter().map(|spec| async move {
+        // Concurrent calls for a symbol queue here; whoever waited reuses the quote the
+        // first one fetched. Even with force, a quote fetched after this call began counts.
+        let requested = Instant::now();
+        let lock = key_lock(&TICKER_FETCHES, &spec.symbol);
+        let _fetching = lock.lock().await;
+        let cached = ticker_cache()
+            .lock()
+            .unwrap()
+            .get(&spec.symbol)
+            .filter(|(at, _)| if force { *at >= requested } else { at.elapsed() < ttl })
+            .map(|(_, ticker)| ticker.clone());
+        if let Some(mut ticker) = cached {
+            ticker.symbol = spec.icon.clone();
+            ticker.label = spec.label.clone();
+            return Ok(ticker);
+        }
+        let result = fetch_ticker(client, spec, epsilon, attempts).await;
+        if let Ok(ticker) = &result {
+            ticker_cache()
+                .lock()
+                .unwrap()
+                .insert(spec.symbol.clone(), (Instant::now(), ticker.clone()));
         }
+        result
+    }))
+    .await;
+
+    specs
+        .iter()
+        .zip(results)
+        .map(|(spec, result)| match result {
+            Ok(ticker) => TickerResult::Ok(ticker),
+            Err(error) => TickerResult::Error {
+                symbol: spec.symbol.clone(),
+                label: spec.label.clone(),
+                icon: spec.icon.clone(),
+                error,
+            },
+        })
+        .collect()
+}
+
+// (unix_ts, close) pairs for a symbol over range/interval, e.g. "2d" / "15m" for a
+// sparkline. Yahoo pads the series with null closes; those points are dropped.
+#[tauri::command]
+async fn fetch_ticker_history(symbol: String, range: String, interval: String) -> Result<Vec<(i64, f64)>, String> {
+    let data = fetch_yahoo_chart(http_client(), &symbol, &range, &interval, DEFAULT_TICKER_FETCH_ATTEMPTS).await?;
+    let chart = &data["chart"];
+    if !chart["error"].is_null() || chart["result"][0].is_null() {
+        return Err(format!("No chart data for {}", symbol));
     }
 
-    // Silver from Yahoo Finance
-    if let Ok(resp) = client.get("https://query2.finance.yahoo.com/v8/finance/chart/SI=F?interval=1d&range=2d")
-        .header("User-Agent", "Mozilla/5.0")
-        .send().await {
-        if let Ok(data) = resp.json::<serde_json::Value>().await {
-            if let Some(meta) = data["chart"]["result"][0]["meta"].as_object() {
-                let price = meta.get("regularMarketPrice").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                let prev = meta.get("chartPreviousClose").and_then(|v| v.as_f64())
-                    .or_else(|| meta.get("previousClose").and_then(|v| v.as_f64())).unwrap_or(0.0);
-                let change = if prev > 0.0 { ((price - prev) / prev) * 100.0 } else { 0.0 };
-                if price > 0.0 {
-                    results.push(TickerData {
-                        symbol: "🪙".into(),
-                        label: "Silver".into(),
-                        price: format!("${:.2}", price),
-                        change,
-                    });
-                }
-            }
-        }
+    let result = &chart["result"][0];
+    let timestamps = result["timestamp"].as_array().cloned().unwrap_or_default();
+    let closes = result["indicators"]["quote"][0]["close"].as_array().cloned().unwrap_or_default();
+
+    Ok(timestamps
+        .iter()
+        .zip(closes.iter())
+        .filter_map(|(ts, close)| Some((ts.as_i64()?, close.as_f64()?)))
+        .collect())
+}
+
+// Biggest absolute percent moves among the configured tickers, largest first
+#[tauri::command]
+async fn get_top_movers(limit: usize) -> Result<Vec<TickerData>, String> {
+    let mut tickers: Vec<TickerData> = load_tickers(&default_ticker_specs(), false, DEFAULT_TICKER_FETCH_ATTEMPTS)
+        .await
+        .into_iter()
+        .filter_map(|result| match result {
+            TickerResult::Ok(ticker) => Some(ticker),
+            TickerResult::Error { .. } => None,
+        })
+        .collect();
+    if tickers.is_empty() {
+        return Err("No ticker quotes available".to_string());
     }
+    // Ties keep a stable alphabetical order so the widget doesn't shuffle between refreshes
+    tickers.sort_by(|a, b| {
+        b.change.abs()
+            .total_cmp(&a.change.abs())
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    tickers.truncate(limit);
+    Ok(tickers)
+}
+
+// sox child for the recording in progress, with the WAV it's writing
+static RECORDING_PROCESS: Mutex<Option<(Child, PathBuf)>> = Mutex::new(None);
 
-    results
+// Microphone control goes through osascript, so it only exists on macOS; elsewhere these
+// commands are stubs that say so instead of failing to spawn
+#[cfg(not(target_os = "macos"))]
+const AUDIO_CONTROL_UNSUPPORTED: &str = "audio control not supported on this platform";
+
+// Current microphone mute state: input volume 0 counts as muted
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn get_input_mute() -> Result<bool, String> {
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg("input volume of (get volume settings)")
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to read input volume: {}", stderr.trim()));
+    }
+    let volume = String::from_utf8_lossy(&output.stdout);
+    let volume: u32 = volume
+        .trim()
+        .parse()
+        .map_err(|_| format!("Unexpected input volume: {}", volume.trim()))?;
+    Ok(volume == 0)
 }
 
-static RECORDING_PROCESS: Mutex<Option<Child>> = Mutex::new(None);
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn get_input_mute() -> Result<bool, String> {
+    Err(AUDIO_CONTROL_UNSUPPORTED.to_string())
+}
 
+#[cfg(target_os = "macos")]
 #[tauri::command]
 fn toggle_input_mute(state: bool) -> Result<String, String> {
     // First attempt: direct command with osascript
@@ -399,12 +2971,85 @@ fn toggle_input_mute(state: bool) -> Result<String, String> {
     }
 }
 
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn toggle_input_mute(state: bool) -> Result<String, String> {
+    // Keep the argument name so invoke() calls look the same on every platform
+    let _ = state;
+    Err(AUDIO_CONTROL_UNSUPPORTED.to_string())
+}
+
+// Flip the microphone mute state as the OS currently reports it; returns the new state
+// (true = muted)
+#[tauri::command]
+fn toggle_input_mute_relative() -> Result<bool, String> {
+    let muted = !get_input_mute()?;
+    toggle_input_mute(muted)?;
+    Ok(muted)
+}
+
+const DEFAULT_MAX_RECORDING_SECS: u64 = 120;
+
+// Path to a voice-input tool: dashboard.<setting> in openclaw.json if set, else the
+// first `name` on PATH, else the Apple-Silicon Homebrew location
+fn voice_tool(name: &str, setting: &str) -> Result<PathBuf, String> {
+    if let Some(path) = dashboard_setting(setting).as_str().filter(|p| !p.trim().is_empty()) {
+        let path = expand_home(path.trim());
+        if path.is_file() {
+            return Ok(path);
+        }
+        return Err(format!("{} not found at {} (dashboard.{})", name, path.display(), setting));
+    }
+    if let Ok(path) = which::which(name) {
+        return Ok(path);
+    }
+    let homebrew = PathBuf::from("/opt/homebrew/bin").join(name);
+    if homebrew.is_file() {
+        return Ok(homebrew);
+    }
+    Err(format!("{} not found; install it or set dashboard.{} in openclaw.json", name, setting))
+}
+
+// Auto-stop watchdog for the current recording: (session id, cancel sender).
+// Dropping the sender wakes the watchdog thread and cancels it.
+static RECORDING_TIMER: Mutex<Option<(u64, mpsc::Sender<()>)>> = Mutex::new(None);
+static RECORDING_SESSION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Clone)]
+struct RecordingAutoStopped {
+    transcript: Option<String>,
+    error: Option<String>,
+}
+
+fn cancel_recording_timer() {
+    RECORDING_TIMER.lock().unwrap().take();
+}
+
 #[tauri::command]
-fn start_voice_input() -> Result<String, String> {
-    let tmp_path = std::env::temp_dir().join("dashboard_voice.wav");
+fn start_voice_input(app: tauri::AppHandle, max_secs: Option<u64>) -> Result<String, String> {
+    let max_secs = max_secs.unwrap_or(DEFAULT_MAX_RECORDING_SECS).max(1);
+
+    // Held until the new session is stored, so two quick starts can't both spawn sox
+    let mut proc = RECORDING_PROCESS.lock().unwrap();
+    if let Some((child, _)) = proc.as_mut() {
+        // A session whose sox already exited (e.g. at the max length) still has an
+        // untranscribed file; replacing it would orphan that recording
+        return Err(match child.try_wait() {
+            Ok(None) => "A recording is already in progress".to_string(),
+            _ => "The last recording stopped on its own and hasn't been transcribed; call stop_voice_input to finish it".to_string(),
+        });
+    }
+
+    // A fresh file per session, so a leftover from a crash (see recover_recordings) or a
+    // second window can never be mixed up with this one
+    let tmp_path = std::env::temp_dir().join(format!(
+        "dashboard_voice-{}-{}.wav",
+        std::process::id(),
+        chrono::Local::now().timestamp_millis()
+    ));
     
     // Start recording with sox
-    let child = Command::new("/opt/homebrew/bin/sox")
+    let child = Command::new(voice_tool("sox", "soxPath")?)
         .args([
             "-d",                           // default input device
             "-r", "16000",                  // 16kHz sample rate (whisper expects this)
@@ -412,105 +3057,452 @@ fn start_voice_input() -> Result<String, String> {
             "-b", "16",                     // 16-bit
             tmp_path.to_str().unwrap(),
         ])
+        // sox stops writing at the limit itself, so the file stays bounded even if
+        // the watchdog below never gets to run
+        .args(["trim", "0", &max_secs.to_string()])
         .spawn()
         .map_err(|e| format!("Failed to start recording: {}", e))?;
     
-    let mut proc = RECORDING_PROCESS.lock().unwrap();
-    *proc = Some(child);
-    
+    *proc = Some((child, tmp_path));
+    drop(proc);
+
+    // Stop and transcribe on our own if the user forgets to
+    let session = RECORDING_SESSION.fetch_add(1, Ordering::SeqCst) + 1;
+    let (cancel_tx, cancel_rx) = mpsc::channel::<()>();
+    *RECORDING_TIMER.lock().unwrap() = Some((session, cancel_tx));
+    let max = Duration::from_secs(max_secs);
+    std::thread::spawn(move || {
+        if cancel_rx.recv_timeout(max) != Err(mpsc::RecvTimeoutError::Timeout) {
+            return;
+        }
+        // Only act if this is still the live session (not stopped or restarted meanwhile)
+        {
+            let mut timer = RECORDING_TIMER.lock().unwrap();
+            if !matches!(*timer, Some((id, _)) if id == session) {
+                return;
+            }
+            *timer = None;
+        }
+        let payload = match finish_recording(None, None) {
+            Ok(transcript) => RecordingAutoStopped { transcript: Some(transcript), error: None },
+            Err(e) => RecordingAutoStopped { transcript: None, error: Some(e) },
+        };
+        let _ = app.emit("recording-auto-stopped", payload);
+    });
+
     Ok("Recording started".to_string())
 }
 
+// Whether sox is still recording. A child that has already exited (e.g. at the trim
+// limit) is reaped; its file stays tracked so stop_voice_input can still transcribe it.
 #[tauri::command]
-fn stop_voice_input() -> Result<String, String> {
-    // Stop the recording
-    {
-        let mut proc = RECORDING_PROCESS.lock().unwrap();
-        if let Some(ref mut child) = *proc {
-            // Send SIGTERM to stop sox gracefully
-            let _ = Command::new("kill")
-                .arg(child.id().to_string())
-                .output();
-            let _ = child.wait();
-        }
-        *proc = None;
+fn is_recording() -> bool {
+    RECORDING_PROCESS
+        .lock()
+        .unwrap()
+        .as_mut()
+        .is_some_and(|(child, _)| matches!(child.try_wait(), Ok(None)))
+}
+
+#[derive(Serialize, Clone)]
+struct TranscriptionProgress {
+    segment: String,
+}
+
+#[derive(Serialize, Clone)]
+struct TranscriptionDone {
+    transcript: Option<String>,
+    error: Option<String>,
+}
+
+// model is a name from list_whisper_models (default "base.en"); language is a whisper
+// language code such as "de", or "auto" to detect it. By default this blocks and returns
+// the transcript. With stream set it returns once sox is stopped, then emits a
+// "transcription-progress" event per segment and a final "transcription-done".
+#[tauri::command]
+fn stop_voice_input(
+    app: tauri::AppHandle,
+    model: Option<String>,
+    language: Option<String>,
+    stream: Option<bool>,
+) -> Result<String, String> {
+    cancel_recording_timer();
+    if !stream.unwrap_or(false) {
+        return finish_recording(model.as_deref(), language.as_deref());
     }
-    
-    let tmp_path = std::env::temp_dir().join("dashboard_voice.wav");
+
+    let tmp_path = stop_recording()?;
+    std::thread::spawn(move || {
+        let result = transcribe(&tmp_path, model.as_deref(), language.as_deref(), |segment| {
+            let _ = app.emit("transcription-progress", TranscriptionProgress { segment: segment.to_string() });
+        });
+        let payload = match result {
+            Ok(transcript) => TranscriptionDone { transcript: Some(transcript), error: None },
+            Err(e) => TranscriptionDone { transcript: None, error: Some(e) },
+        };
+        let _ = app.emit("transcription-done", payload);
+    });
+    Ok("Transcribing".to_string())
+}
+
+const DEFAULT_WHISPER_MODEL: &str = "base.en";
+
+fn whisper_models_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_default();
+    PathBuf::from(&home).join(".local/share/whisper")
+}
+
+// Installed whisper models by short name ("base.en", "small", ...) from ggml-<name>.bin
+#[tauri::command]
+fn list_whisper_models() -> Vec<String> {
+    let mut models: Vec<String> = fs::read_dir(whisper_models_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    name.strip_prefix("ggml-")
+                        .and_then(|n| n.strip_suffix(".bin"))
+                        .map(|n| n.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    models.sort();
+    models
+}
+
+// Stop sox (if running) and return the recording it left behind
+fn stop_recording() -> Result<PathBuf, String> {
+    // Stop the recording
+    let (mut child, tmp_path) = RECORDING_PROCESS
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or("No recording in progress")?;
+    // Send SIGTERM to stop sox gracefully
+    let _ = Command::new("kill")
+        .arg(child.id().to_string())
+        .output();
+    let _ = child.wait();
     
     if !tmp_path.exists() {
         return Err("No recording found".to_string());
     }
     
+    Ok(tmp_path)
+}
+
+// Stop sox (if running) and transcribe whatever was recorded
+fn finish_recording(model: Option<&str>, language: Option<&str>) -> Result<String, String> {
+    let tmp_path = stop_recording()?;
+    transcribe(&tmp_path, model, language, |_| {})
+}
+
+// A line of whisper-cli output that is part of the transcript rather than logging
+fn transcript_segment(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    let keep = !trimmed.is_empty() 
+        && !trimmed.contains("whisper_") 
+        && !trimmed.contains("system_info")
+        && !trimmed.contains("ggml_")
+        && !trimmed.contains("main:")
+        && trimmed != "[BLANK_AUDIO]";
+    keep.then_some(trimmed)
+}
+
+// Run whisper over a recording, deleting it afterwards. on_segment sees each transcript
+// segment as whisper prints it.
+fn transcribe(
+    tmp_path: &std::path::Path,
+    model: Option<&str>,
+    language: Option<&str>,
+    mut on_segment: impl FnMut(&str),
+) -> Result<String, String> {
     // Transcribe with whisper-cpp
-    let home = std::env::var("HOME").unwrap_or_default();
-    let model_path = format!("{}/.local/share/whisper/ggml-base.en.bin", home);
-    
-    let output = Command::new("/opt/homebrew/bin/whisper-cli")
-        .args([
-            "--model", &model_path,
-            "--no-timestamps",
-            "--no-prints",
-            "--file", tmp_path.to_str().unwrap(),
-        ])
-        .output()
+    let model = model.unwrap_or(DEFAULT_WHISPER_MODEL);
+    let model_path = whisper_models_dir().join(format!("ggml-{}.bin", model));
+    if model.contains(['/', '\\']) || !model_path.is_file() {
+        return Err(format!(
+            "Whisper model \"{}\" is not installed; available: {}",
+            model,
+            list_whisper_models().join(", ")
+        ));
+    }
+
+    let mut cmd = Command::new(voice_tool("whisper-cli", "whisperPath")?);
+    cmd.args([
+        "--model", model_path.to_str().unwrap(),
+        "--no-timestamps",
+        "--no-prints",
+        "--file", tmp_path.to_str().unwrap(),
+    ]);
+    if let Some(language) = language.filter(|l| !l.trim().is_empty()) {
+        cmd.args(["--language", language.trim()]);
+    }
+    let mut child = cmd
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
         .map_err(|e| format!("Failed to run whisper: {}", e))?;
+
+    // Drain stderr on its own thread so whisper can't stall on a full pipe
+    let stderr = child.stderr.take().map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut text = String::new();
+            let _ = std::io::Read::read_to_string(&mut pipe, &mut text);
+            text
+        })
+    });
+
+    // whisper-cli prints each segment as soon as it's decoded
+    let mut segments: Vec<String> = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        use std::io::BufRead;
+        for line in std::io::BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Some(segment) = transcript_segment(&line) {
+                on_segment(segment);
+                segments.push(segment.to_string());
+            }
+        }
+    }
+    let status = child.wait().map_err(|e| format!("Failed to run whisper: {}", e))?;
+    let stderr = stderr.and_then(|handle| handle.join().ok()).unwrap_or_default();
     
     // Clean up the temp file
-    let _ = fs::remove_file(&tmp_path);
+    let _ = fs::remove_file(tmp_path);
     
-    if output.status.success() {
-        let transcript = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .filter(|l| {
-                let trimmed = l.trim();
-                !trimmed.is_empty() 
-                    && !trimmed.contains("whisper_") 
-                    && !trimmed.contains("system_info")
-                    && !trimmed.contains("ggml_")
-                    && !trimmed.contains("main:")
-                    && trimmed != "[BLANK_AUDIO]"
-            })
-            .collect::<Vec<_>>()
-            .join(" ")
-            .trim()
-            .to_string();
-        Ok(transcript)
+    if status.success() {
+        Ok(segments.join(" ").trim().to_string())
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
         Err(format!("Transcription failed: {}", stderr))
     }
 }
 
+// A dashboard recording in the temp dir, and nothing else, so these commands can't touch
+// arbitrary files
+fn recording_file(path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path);
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let in_temp = path.parent().and_then(|p| fs::canonicalize(p).ok())
+        == fs::canonicalize(std::env::temp_dir()).ok();
+    if !in_temp || !name.starts_with("dashboard_voice") || !name.ends_with(".wav") {
+        return Err(format!("Not a dashboard recording: {}", path.display()));
+    }
+    if !path.is_file() {
+        return Err(format!("Recording not found: {}", path.display()));
+    }
+    Ok(path)
+}
+
+// Recordings left behind by a crash mid-recording. The frontend calls this on mount and
+// offers each one to transcribe_recording or discard_recording. Files still being written,
+// by this process or another running instance (the pid is in the name), are left out.
 #[tauri::command]
-async fn speak_text(text: String) -> Result<String, String> {
-    let tmp_path = std::env::temp_dir().join("larry_tts.wav");
+fn recover_recordings() -> Vec<String> {
+    let current = RECORDING_PROCESS.lock().unwrap().as_ref().map(|(_, path)| path.clone());
+    let own_pid = std::process::id();
+    let mut paths: Vec<String> = fs::read_dir(std::env::temp_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let Some(stem) = name.strip_prefix("dashboard_voice").and_then(|n| n.strip_suffix(".wav")) else {
+                        return false;
+                    };
+                    // "-<pid>-<millis>"; older builds used a bare "dashboard_voice.wav"
+                    let pid = stem
+                        .strip_prefix('-')
+                        .and_then(|rest| rest.split('-').next())
+                        .and_then(|pid| pid.parse::<u32>().ok());
+                    let live_elsewhere = pid.is_some_and(|pid| pid != own_pid && process_running(pid));
+                    current.as_ref() != Some(&entry.path()) && !live_elsewhere
+                })
+                .map(|entry| entry.path().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+    paths
+}
+
+fn process_running(pid: u32) -> bool {
+    let pid = sysinfo::Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::Some(&[pid]),
+        true,
+        sysinfo::ProcessRefreshKind::new(),
+    );
+    sys.process(pid).is_some()
+}
+
+// Whisper can take several seconds, so it runs on a blocking worker rather than the
+// main thread that sync commands use
+#[tauri::command]
+async fn transcribe_recording(path: String, model: Option<String>, language: Option<String>) -> Result<String, String> {
+    let path = recording_file(&path)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        transcribe(&path, model.as_deref(), language.as_deref(), |_| {})
+    })
+    .await
+    .map_err(|e| format!("Transcription task failed: {}", e))?
+}
+
+#[tauri::command]
+fn discard_recording(path: String) -> Result<(), String> {
+    let path = recording_file(&path)?;
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete recording: {}", e))
+}
+
+// Append a transcript under a timestamp heading in workspace/journal/YYYY-MM-DD.md
+#[tauri::command]
+fn save_voice_note(text: String) -> Result<String, String> {
+    use std::io::Write;
+
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Voice note is empty".to_string());
+    }
+
     let home = std::env::var("HOME").unwrap_or_default();
-    let model_dir = format!("{}/.local/share/sherpa-onnx-tts/vits-piper-en_US-lessac-medium", home);
-    
-    // Use sherpa-onnx via Python for local TTS
-    let script = format!(
-        r#"
+    let journal_dir = PathBuf::from(&home).join(".openclaw/workspace/journal");
+    fs::create_dir_all(&journal_dir)
+        .map_err(|e| format!("Failed to create journal dir: {}", e))?;
+
+    let now = chrono::Local::now();
+    let date = now.format("%Y-%m-%d").to_string();
+    let path = journal_dir.join(format!("{}.md", date));
+
+    let mut entry = String::new();
+    if !path.exists() {
+        entry.push_str(&format!("# {}\n", date));
+    }
+    entry.push_str(&format!("\n## {}\n\n{}\n", now.format("%H:%M"), text));
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open journal file: {}", e))?;
+    file.write_all(entry.as_bytes())
+        .map_err(|e| format!("Failed to write journal file: {}", e))?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+// Player child (see play_wav) for the utterance currently playing, with its text
+static SPEECH_PLAYBACK: Mutex<Option<(Child, String)>> = Mutex::new(None);
+
+#[derive(Serialize)]
+pub struct SpeechStatus {
+    playing: bool,
+    text: Option<String>,
+}
+
+#[tauri::command]
+fn get_speech_status() -> SpeechStatus {
+    let mut guard = SPEECH_PLAYBACK.lock().unwrap();
+    let playing = guard
+        .as_mut()
+        .is_some_and(|(child, _)| matches!(child.try_wait(), Ok(None)));
+    if !playing {
+        *guard = None;
+    }
+    SpeechStatus {
+        playing,
+        text: guard.as_ref().map(|(_, text)| text.clone()),
+    }
+}
+
+// Kill the player for the current utterance, if any. Not an error when nothing is playing.
+#[tauri::command]
+fn stop_speaking() -> Result<(), String> {
+    if let Some((mut child, _)) = SPEECH_PLAYBACK.lock().unwrap().take() {
+        if matches!(child.try_wait(), Ok(None)) {
+            child.kill().map_err(|e| format!("Failed to stop playback: {}", e))?;
+        }
+        let _ = child.wait();
+    }
+    Ok(())
+}
+
+fn tts_voices_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_default();
+    PathBuf::from(&home).join(".local/share/sherpa-onnx-tts")
+}
+
+const DEFAULT_TTS_VOICE: &str = "vits-piper-en_US-lessac-medium";
+
+// Installed sherpa-onnx voices: model dirs under ~/.local/share/sherpa-onnx-tts with an .onnx file
+#[tauri::command]
+fn list_tts_voices() -> Vec<String> {
+    let mut voices: Vec<String> = fs::read_dir(tts_voices_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| tts_model_file(&entry.path()).is_some())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    voices.sort();
+    voices
+}
+
+fn tts_model_file(voice_dir: &std::path::Path) -> Option<PathBuf> {
+    fs::read_dir(voice_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "onnx"))
+}
+
+// argv: text, model file, model dir, output wav path, length_scale
+const TTS_SCRIPT: &str = r#"
+import sys
 import sherpa_onnx, soundfile as sf
+text, model, model_dir, out, length_scale = sys.argv[1:6]
 tts = sherpa_onnx.OfflineTts(sherpa_onnx.OfflineTtsConfig(
     model=sherpa_onnx.OfflineTtsModelConfig(
         vits=sherpa_onnx.OfflineTtsVitsModelConfig(
-            model='{model_dir}/en_US-lessac-medium.onnx',
-            tokens='{model_dir}/tokens.txt',
-            data_dir='{model_dir}/espeak-ng-data',
+            model=model,
+            tokens=f'{model_dir}/tokens.txt',
+            data_dir=f'{model_dir}/espeak-ng-data',
+            length_scale=float(length_scale),
         ),
     ),
 ))
-audio = tts.generate('''{text}''')
-sf.write('{out}', audio.samples, audio.sample_rate)
-"#,
-        model_dir = model_dir,
-        text = text.replace('\'', "\\'").replace('\n', " "),
-        out = tmp_path.to_str().unwrap(),
-    );
+audio = tts.generate(text)
+sf.write(out, audio.samples, audio.sample_rate)
+"#;
+
+// Render text to a WAV at out. voice is a model dir name from list_tts_voices (default
+// lessac-medium); speed is a rate multiplier (1.0 = normal, 2.0 = twice as fast), passed
+// to sherpa as length_scale = 1/speed.
+fn synthesize_speech(text: &str, voice: Option<String>, speed: Option<f32>, out: &std::path::Path) -> Result<(), String> {
+    let voice = voice.unwrap_or_else(|| DEFAULT_TTS_VOICE.to_string());
+    let model_dir = tts_voices_dir().join(&voice);
+    // Voice names are single directory names; anything with a separator can't be one
+    let model = if voice.contains(['/', '\\']) { None } else { tts_model_file(&model_dir) };
+    let model = model.ok_or_else(|| {
+        format!("TTS voice \"{}\" is not installed; available: {}", voice, list_tts_voices().join(", "))
+    })?;
+    let speed = speed.unwrap_or(1.0);
+    if !(speed.is_finite() && speed > 0.0) {
+        return Err(format!("Invalid TTS speed: {}", speed));
+    }
     
+    // Use sherpa-onnx via Python for local TTS. The text, model dir and output path go
+    // in as argv so nothing user-supplied is ever spliced into the script source.
     let output = Command::new("python3")
-        .args(["-c", &script])
+        .args(["-c", TTS_SCRIPT])
+        .arg(text.replace('\n', " "))
+        .arg(&model)
+        .arg(&model_dir)
+        .arg(out)
+        .arg((1.0 / speed).to_string())
         .output()
         .map_err(|e| format!("Failed to run TTS: {}", e))?;
     
@@ -518,49 +3510,82 @@ sf.write('{out}', audio.samples, audio.sample_rate)
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("TTS failed: {}", stderr));
     }
+    Ok(())
+}
+
+// Start playing a WAV with the platform's command-line player: afplay on macOS, a
+// SoundPlayer via PowerShell on Windows, and paplay (PulseAudio/PipeWire) falling back
+// to aplay (ALSA) elsewhere
+fn play_wav(path: &std::path::Path) -> std::io::Result<Child> {
+    if cfg!(target_os = "macos") {
+        Command::new("afplay").arg(path).spawn()
+    } else if cfg!(target_os = "windows") {
+        // Path goes through the environment so it never needs quoting inside the script
+        Command::new("powershell")
+            .args(["-NoProfile", "-Command", "(New-Object Media.SoundPlayer $env:DASHBOARD_TTS_WAV).PlaySync()"])
+            .env("DASHBOARD_TTS_WAV", path)
+            .spawn()
+    } else {
+        Command::new("paplay")
+            .arg(path)
+            .spawn()
+            .or_else(|_| Command::new("aplay").arg("-q").arg(path).spawn())
+    }
+}
+
+// Synthesize and play, replacing whatever is already speaking
+#[tauri::command]
+async fn speak_text(text: String, voice: Option<String>, speed: Option<f32>) -> Result<String, String> {
+    let tmp_path = std::env::temp_dir().join("larry_tts.wav");
+    synthesize_speech(&text, voice, speed, &tmp_path)?;
     
-    // Kill any existing TTS playback before starting new one
-    let _ = Command::new("pkill").args(["-f", "afplay.*larry_tts"]).output();
+    // Stop any existing TTS playback before starting the new one
+    stop_speaking()?;
     
     // Play the audio
-    Command::new("afplay")
-        .arg(tmp_path.to_str().unwrap())
-        .spawn()
-        .map_err(|e| format!("Failed to play audio: {}", e))?;
+    let child = play_wav(&tmp_path).map_err(|e| format!("Failed to play audio: {}", e))?;
+    *SPEECH_PLAYBACK.lock().unwrap() = Some((child, text));
     
     Ok("Speaking".to_string())
 }
 
+// Synthesize without playing and return the WAV path, for the webview to play in an
+// <audio> element via convertFileSrc (the asset protocol is scoped to these temp files).
+// Each call gets a fresh file name so the webview never replays a cached earlier clip;
+// clips from previous calls are removed.
+#[tauri::command]
+async fn synthesize_text(text: String, voice: Option<String>, speed: Option<f32>) -> Result<String, String> {
+    let tmp_dir = std::env::temp_dir();
+    if let Ok(entries) = fs::read_dir(&tmp_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("larry_tts-") && name.ends_with(".wav") {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    let out = tmp_dir.join(format!("larry_tts-{}.wav", chrono::Local::now().timestamp_millis()));
+    synthesize_speech(&text, voice, speed, &out)?;
+    Ok(out.to_string_lossy().to_string())
+}
+
 #[tauri::command]
 async fn fetch_metals_spots() -> Result<String, String> {
-    let client = reqwest::Client::new();
+    let client = http_client();
     let mut result = serde_json::Map::new();
 
     // Gold futures (GC=F)
-    if let Ok(resp) = client
-        .get("https://query2.finance.yahoo.com/v8/finance/chart/GC%3DF?interval=1d&range=1d")
-        .header("User-Agent", "Mozilla/5.0")
-        .send()
-        .await
-    {
-        if let Ok(data) = resp.json::<serde_json::Value>().await {
-            if let Some(price) = data["chart"]["result"][0]["meta"]["regularMarketPrice"].as_f64() {
-                result.insert("gold".to_string(), serde_json::json!(price));
-            }
+    if let Ok(data) = fetch_yahoo_chart(client, "GC=F", "1d", "1d", 1).await {
+        if let Some(price) = data["chart"]["result"][0]["meta"]["regularMarketPrice"].as_f64() {
+            result.insert("gold".to_string(), serde_json::json!(price));
         }
     }
 
     // Silver futures (SI=F)
-    if let Ok(resp) = client
-        .get("https://query2.finance.yahoo.com/v8/finance/chart/SI%3DF?interval=1d&range=1d")
-        .header("User-Agent", "Mozilla/5.0")
-        .send()
-        .await
-    {
-        if let Ok(data) = resp.json::<serde_json::Value>().await {
-            if let Some(price) = data["chart"]["result"][0]["meta"]["regularMarketPrice"].as_f64() {
-                result.insert("silver".to_string(), serde_json::json!(price));
-            }
+    if let Ok(data) = fetch_yahoo_chart(client, "SI=F", "1d", "1d", 1).await {
+        if let Some(price) = data["chart"]["result"][0]["meta"]["regularMarketPrice"].as_f64() {
+            result.insert("silver".to_string(), serde_json::json!(price));
         }
     }
 
@@ -571,34 +3596,42 @@ async fn fetch_metals_spots() -> Result<String, String> {
     serde_json::to_string(&result).map_err(|e| format!("JSON error: {}", e))
 }
 
+// Refresh Coinbase balances natively. Credentials come from the arguments, else
+// dashboard.coinbaseApiKey / dashboard.coinbaseApiSecret in openclaw.json; with neither
+// configured it falls back to the fetch-coinbase.py helper, as before.
 #[tauri::command]
-async fn fetch_coinbase() -> Result<String, String> {
-    let output = Command::new("python3")
-        .arg("/Users/jadmin/.config/finance-dashboard/fetch-coinbase.py")
-        .output()
-        .map_err(|e| format!("Failed to run fetch: {}", e))?;
-    
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Fetch failed: {}", stderr));
+async fn fetch_coinbase(api_key: Option<String>, api_secret: Option<String>) -> Result<String, String> {
+    let setting = |key: &str| dashboard_setting(key).as_str().map(|s| s.to_string());
+    let api_key = api_key.or_else(|| setting("coinbaseApiKey"));
+    let api_secret = api_secret.or_else(|| setting("coinbaseApiSecret"));
+    match (api_key, api_secret) {
+        (Some(key), Some(secret)) => fetch_coinbase_native(key, secret).await,
+        (None, None) => {
+            let config = finance_config();
+            run_finance_script(&config.python, &config.coinbase_script)
+        }
+        (None, Some(_)) => Err("Coinbase API key not configured".to_string()),
+        (Some(_), None) => Err("Coinbase API secret not configured".to_string()),
     }
-    
-    String::from_utf8(output.stdout)
-        .map_err(|e| format!("Invalid UTF-8: {}", e))
 }
 
 #[tauri::command]
 async fn read_coinbase_data() -> Result<String, String> {
-    let path = format!("{}/.config/finance-dashboard/coinbase-balances.json",
-        std::env::var("HOME").unwrap_or_default());
+    let path = finance_dir().join("coinbase-balances.json");
     std::fs::read_to_string(&path)
         .map_err(|e| format!("Failed to read: {}", e))
 }
 
 #[tauri::command]
 async fn fetch_strike() -> Result<String, String> {
-    let output = Command::new("python3")
-        .arg("/Users/jadmin/.config/finance-dashboard/fetch-strike.py")
+    let config = finance_config();
+    run_finance_script(&config.python, &config.strike_script)
+}
+
+// Run one of the python fetch helpers and return what it prints
+fn run_finance_script(python: &str, script: &std::path::Path) -> Result<String, String> {
+    let output = Command::new(python)
+        .arg(script)
         .output()
         .map_err(|e| format!("Failed to run fetch: {}", e))?;
     
@@ -613,153 +3646,529 @@ async fn fetch_strike() -> Result<String, String> {
 
 #[tauri::command]
 async fn read_strike_data() -> Result<String, String> {
-    let path = format!("{}/.config/finance-dashboard/strike-balances.json",
-        std::env::var("HOME").unwrap_or_default());
+    let path = finance_dir().join("strike-balances.json");
     std::fs::read_to_string(&path)
         .map_err(|e| format!("Failed to read: {}", e))
 }
 
-// ─── SnapTrade: signed requests from Rust to avoid CORS ──────────────────────
+// ─── Coinbase: signed requests from Rust (no python helper) ──────────────────
 
-#[tauri::command]
-async fn fetch_snaptrade_accounts(
-    client_id: String,
-    consumer_key: String,
-    user_id: String,
-    user_secret: String,
-) -> Result<String, String> {
+const COINBASE_API: &str = "https://api.coinbase.com";
+const COINBASE_MAX_PAGES: usize = 20;
+
+// HMAC-SHA256 shared by the Coinbase and SnapTrade request signers
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
     use hmac::{Hmac, Mac};
     use sha2::Sha256;
-    use base64::{Engine as _, engine::general_purpose};
 
+    let mut mac = Hmac::<Sha256>::new_from_slice(key)
+        .map_err(|e| format!("HMAC init error: {}", e))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+// Where the finance widgets keep cached balances and helper scripts. Each field can be
+// set under dashboard.finance in openclaw.json ("~/" is expanded); the data directory
+// also honours $FINANCE_DASHBOARD_DIR. Defaults live under ~/.config/finance-dashboard,
+// except Fidelity exports, which are looked for in ~/Downloads.
+struct FinanceConfig {
+    dir: PathBuf,
+    python: String,
+    strike_script: PathBuf,
+    coinbase_script: PathBuf,
+    fidelity_dir: PathBuf,
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_default();
+    match path.strip_prefix("~/") {
+        Some(rest) => PathBuf::from(home).join(rest),
+        None if path == "~" => PathBuf::from(home),
+        None => PathBuf::from(path),
+    }
+}
+
+fn finance_config() -> FinanceConfig {
+    let settings = dashboard_setting("finance");
+    let setting = |key: &str| settings[key].as_str().map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let dir = setting("dir")
+        .or_else(|| std::env::var("FINANCE_DASHBOARD_DIR").ok().filter(|s| !s.is_empty()))
+        .map(|d| expand_home(&d))
+        .unwrap_or_else(|| expand_home("~/.config/finance-dashboard"));
+    let strike_script = setting("strikeScript")
+        .map(|p| expand_home(&p))
+        .unwrap_or_else(|| dir.join("fetch-strike.py"));
+    let coinbase_script = setting("coinbaseScript")
+        .map(|p| expand_home(&p))
+        .unwrap_or_else(|| dir.join("fetch-coinbase.py"));
+
+    FinanceConfig {
+        python: setting("python").unwrap_or_else(|| "python3".to_string()),
+        fidelity_dir: expand_home(&setting("fidelityDir").unwrap_or_else(|| "~/Downloads".to_string())),
+        strike_script,
+        coinbase_script,
+        dir,
+    }
+}
+
+fn finance_dir() -> PathBuf {
+    finance_config().dir
+}
+
+#[derive(Serialize)]
+struct CoinbaseHolding {
+    currency: String,
+    name: String,
+    balance: f64,
+    usd_value: f64,
+    price_usd: f64,
+}
+
+// Same shape fetch-coinbase.py writes to coinbase-balances.json
+#[derive(Serialize)]
+struct CoinbaseBalances {
+    provider: String,
+    fetched_at: String,
+    total_usd: f64,
+    account_count: usize,
+    holdings: Vec<CoinbaseHolding>,
+}
+
+// API-key auth: CB-ACCESS-SIGN = hex(HMAC-SHA256(secret, timestamp + method + path + body))
+async fn coinbase_get(
+    client: &reqwest::Client,
+    api_key: &str,
+    api_secret: &str,
+    path: &str,
+) -> Result<serde_json::Value, String> {
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_secs()
         .to_string();
+    let prehash = format!("{}GET{}", timestamp, path);
+    let signature = hex::encode(hmac_sha256(api_secret.as_bytes(), prehash.as_bytes())?);
+
+    let resp = client
+        .get(format!("{}{}", COINBASE_API, path))
+        .header("CB-ACCESS-KEY", api_key)
+        .header("CB-ACCESS-SIGN", signature)
+        .header("CB-ACCESS-TIMESTAMP", &timestamp)
+        .header("CB-VERSION", "2024-01-01")
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Coinbase fetch error: {}", request_error(e)))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status().as_u16();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("Coinbase HTTP {}: {}", status, body));
+    }
+
+    resp.json()
+        .await
+        .map_err(|e| format!("Coinbase parse error: {}", request_error(e)))
+}
+
+#[tauri::command]
+async fn fetch_coinbase_native(api_key: String, api_secret: String) -> Result<String, String> {
+    let client = http_client();
+
+    // Accounts are paginated via pagination.next_uri
+    let mut accounts: Vec<serde_json::Value> = Vec::new();
+    let mut next_path = Some("/v2/accounts?limit=100".to_string());
+    for _ in 0..COINBASE_MAX_PAGES {
+        let Some(path) = next_path.take() else { break };
+        let page = coinbase_get(client, &api_key, &api_secret, &path).await?;
+        accounts.extend(page["data"].as_array().cloned().unwrap_or_default());
+        next_path = page["pagination"]["next_uri"]
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+    }
+
+    // Public rate table: units of each currency per 1 USD
+    let rates: serde_json::Value = client
+        .get(format!("{}/v2/exchange-rates?currency=USD", COINBASE_API))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Coinbase rates fetch error: {}", request_error(e)))?
+        .json()
+        .await
+        .map_err(|e| format!("Coinbase rates parse error: {}", request_error(e)))?;
+    let rates = &rates["data"]["rates"];
+
+    let mut holdings: Vec<CoinbaseHolding> = Vec::new();
+    for acct in &accounts {
+        let balance = acct["balance"]["amount"]
+            .as_str()
+            .and_then(|s| s.parse::<f64>().ok())
+            .unwrap_or(0.0);
+        if balance <= 0.0 {
+            continue;
+        }
+        let currency = acct["balance"]["currency"].as_str().unwrap_or("").to_string();
+        let rate = rates[currency.as_str()]
+            .as_str()
+            .and_then(|s| s.parse::<f64>().ok())
+            .unwrap_or(0.0);
+        let price_usd = if rate > 0.0 { 1.0 / rate } else { 0.0 };
+        let name = acct["currency"]["name"]
+            .as_str()
+            .or_else(|| acct["name"].as_str())
+            .unwrap_or(&currency)
+            .to_string();
+        holdings.push(CoinbaseHolding {
+            currency,
+            name,
+            balance,
+            usd_value: balance * price_usd,
+            price_usd,
+        });
+    }
+    holdings.sort_by(|a, b| b.usd_value.total_cmp(&a.usd_value));
+
+    let data = CoinbaseBalances {
+        provider: "coinbase".to_string(),
+        fetched_at: chrono::Utc::now().to_rfc3339(),
+        total_usd: holdings.iter().map(|h| h.usd_value).sum(),
+        account_count: accounts.len(),
+        holdings,
+    };
+    let json = serde_json::to_string_pretty(&data)
+        .map_err(|e| format!("JSON error: {}", e))?;
+
+    // Cache where read_coinbase_data looks for it
+    let dir = finance_dir();
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    fs::write(dir.join("coinbase-balances.json"), &json)
+        .map_err(|e| format!("Failed to cache Coinbase balances: {}", e))?;
+
+    Ok(json)
+}
+
+// ─── Cached finance data with freshness ──────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct CachedFinanceData {
+    data: serde_json::Value,
+    fetched_at: String,
+    // Seconds since fetched_at, for "updated 2m ago"
+    age_secs: u64,
+    // True when this call went to the network
+    refreshed: bool,
+}
+
+// Read a balances file from finance_dir with its fetch time: the file's own fetched_at
+// if it has one, else its modification time
+fn read_cached_finance(file: &str) -> Option<(serde_json::Value, chrono::DateTime<chrono::Utc>)> {
+    let path = finance_dir().join(file);
+    let data: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+    let fetched_at = data["fetched_at"]
+        .as_str()
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .or_else(|| fs::metadata(&path).and_then(|m| m.modified()).ok().map(chrono::DateTime::from))?;
+    Some((data, fetched_at))
+}
+
+// Serve file from the cache while it's younger than max_age_secs, otherwise run fetch
+// (which is expected to rewrite the file) and return what it produced
+async fn cached_finance<F>(file: &str, max_age_secs: u64, fetch: F) -> Result<CachedFinanceData, String>
+where
+    F: std::future::Future<Output = Result<String, String>>,
+{
+    if let Some((data, fetched_at)) = read_cached_finance(file) {
+        let age_secs = (chrono::Utc::now() - fetched_at).num_seconds().max(0) as u64;
+        if age_secs <= max_age_secs {
+            return Ok(CachedFinanceData { data, fetched_at: fetched_at.to_rfc3339(), age_secs, refreshed: false });
+        }
+    }
+
+    let json = fetch.await?;
+    let data: serde_json::Value = serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse {}: {}", file, e))?;
+    Ok(CachedFinanceData {
+        data,
+        fetched_at: chrono::Utc::now().to_rfc3339(),
+        age_secs: 0,
+        refreshed: true,
+    })
+}
+
+// Coinbase balances, re-fetched only when the cached copy is older than max_age_secs
+#[tauri::command]
+async fn get_coinbase(max_age_secs: u64) -> Result<CachedFinanceData, String> {
+    cached_finance("coinbase-balances.json", max_age_secs, fetch_coinbase(None, None)).await
+}
+
+// Strike balances, re-fetched only when the cached copy is older than max_age_secs
+#[tauri::command]
+async fn get_strike(max_age_secs: u64) -> Result<CachedFinanceData, String> {
+    cached_finance("strike-balances.json", max_age_secs, fetch_strike()).await
+}
 
-    // Query string — all 4 params in URL, per SnapTrade SDK
-    let query_string = format!(
+// ─── SnapTrade: signed requests from Rust to avoid CORS ──────────────────────
+
+const SNAPTRADE_API: &str = "https://api.snaptrade.com";
+const SNAPTRADE_PAGE_LIMIT: usize = 100;
+const SNAPTRADE_MAX_PAGES: usize = 50;
+
+struct SnapTradeAuth<'a> {
+    client_id: &'a str,
+    consumer_key: &'a str,
+    user_id: &'a str,
+    user_secret: &'a str,
+}
+
+// Signed GET. All 4 auth params go in the URL, per SnapTrade SDK, followed by any
+// extra params; the signature covers the full query string as sent. Each call takes
+// its own timestamp, since SnapTrade rejects requests signed too long ago.
+async fn snaptrade_get(
+    client: &reqwest::Client,
+    auth: &SnapTradeAuth<'_>,
+    path: &str,
+    extra_query: &[(&str, String)],
+) -> Result<serde_json::Value, String> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        .to_string();
+    let mut query_string = format!(
         "clientId={}&timestamp={}&userId={}&userSecret={}",
-        client_id, timestamp, user_id, user_secret
+        auth.client_id, timestamp, auth.user_id, auth.user_secret
     );
+    for (key, value) in extra_query {
+        query_string.push_str(&format!("&{}={}", key, value));
+    }
 
     // Sign a request: HMAC-SHA256(key=consumerKey, data=JSON sig_object) → base64 STANDARD
     // sig_object keys must be alphabetically ordered: content, path, query
     // content must be null (not {}) for GET requests with no body
-    let make_sig = |path: &str| -> Result<String, String> {
-        let sig_content = format!(
-            r#"{{"content":null,"path":"{}","query":"{}"}}"#,
-            path, query_string
-        );
-        let mut mac = Hmac::<Sha256>::new_from_slice(consumer_key.as_bytes())
-            .map_err(|e| format!("HMAC init error: {}", e))?;
-        mac.update(sig_content.as_bytes());
-        Ok(general_purpose::STANDARD.encode(mac.finalize().into_bytes()))
-    };
-
-    let client = reqwest::Client::new();
-
-    // Fetch accounts list — each path gets its own signature
-    let accounts_path = "/api/v1/accounts";
-    let accounts_url = format!("https://api.snaptrade.com{}?{}", accounts_path, query_string);
-    let accounts_sig = make_sig(accounts_path)?;
+    let sig_content = format!(
+        r#"{{"content":null,"path":"{}","query":"{}"}}"#,
+        path, query_string
+    );
+    let mac = hmac_sha256(auth.consumer_key.as_bytes(), sig_content.as_bytes())?;
+    let signature = general_purpose::STANDARD.encode(mac);
 
-    let accounts_resp = client
-        .get(&accounts_url)
-        .header("Client-Id", &client_id)
+    let resp = client
+        .get(format!("{}{}?{}", SNAPTRADE_API, path, query_string))
+        .header("Client-Id", auth.client_id)
         .header("Timestamp", &timestamp)
-        .header("Signature", &accounts_sig)
+        .header("Signature", &signature)
         .header("Accept", "application/json")
         .send()
         .await
-        .map_err(|e| format!("accounts fetch error: {}", e))?;
+        .map_err(|e| format!("{} fetch error: {}", path, request_error(e)))?;
 
-    if !accounts_resp.status().is_success() {
-        let status = accounts_resp.status().as_u16();
-        let body = accounts_resp.text().await.unwrap_or_default();
-        return Err(format!("accounts HTTP {}: {}", status, body));
+    if !resp.status().is_success() {
+        let status = resp.status().as_u16();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("{} HTTP {}: {}", path, status, body));
     }
 
-    let accounts: serde_json::Value = accounts_resp
-        .json()
+    resp.json()
         .await
-        .map_err(|e| format!("accounts parse error: {}", e))?;
+        .map_err(|e| format!("{} parse error: {}", path, request_error(e)))
+}
+
+// Signed GET over every page of a list endpoint, following offset/limit. Pages are
+// either a bare array or {data: [...], pagination: {total}}.
+async fn snaptrade_get_all(
+    client: &reqwest::Client,
+    auth: &SnapTradeAuth<'_>,
+    path: &str,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut items: Vec<serde_json::Value> = Vec::new();
+
+    for _ in 0..SNAPTRADE_MAX_PAGES {
+        let query = [
+            ("offset", items.len().to_string()),
+            ("limit", SNAPTRADE_PAGE_LIMIT.to_string()),
+        ];
+        let body = snaptrade_get(client, auth, path, &query).await?;
+        let (page, total) = match body {
+            serde_json::Value::Array(page) => (page, None),
+            other => (
+                other["data"].as_array().cloned().unwrap_or_default(),
+                other["pagination"]["total"].as_u64(),
+            ),
+        };
+
+        // A server that ignores offset hands back the same page again
+        if !items.is_empty() && page.first() == items.first() {
+            return Ok(items);
+        }
+
+        let page_len = page.len();
+        items.extend(page);
+        // A short page is the last one; a long one means limit was ignored
+        let done = page_len != SNAPTRADE_PAGE_LIMIT
+            || total.is_some_and(|t| items.len() as u64 >= t);
+        if done {
+            return Ok(items);
+        }
+    }
+
+    eprintln!("{}: stopped after {} pages", path, SNAPTRADE_MAX_PAGES);
+    Ok(items)
+}
+
+// Typed views of the SnapTrade fields the dashboard reads. Everything else is kept in
+// `extra` and serialized back out alongside, so the frontend still sees the full object.
+#[derive(Serialize, Deserialize)]
+pub struct SnapAccount {
+    #[serde(default)]
+    id: String,
+    name: Option<String>,
+    number: Option<String>,
+    institution_name: Option<String>,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapCurrency {
+    code: String,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapBalance {
+    currency: Option<SnapCurrency>,
+    cash: Option<f64>,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+// position.symbol.symbol is the security itself; the outer object is SnapTrade's
+// per-account wrapper around it
+#[derive(Serialize, Deserialize)]
+pub struct SnapSecurity {
+    symbol: String,
+    description: Option<String>,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapPositionSymbol {
+    symbol: Option<SnapSecurity>,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapPosition {
+    symbol: Option<SnapPositionSymbol>,
+    units: Option<f64>,
+    fractional_units: Option<f64>,
+    price: Option<f64>,
+    average_purchase_price: Option<f64>,
+    // What price (and so market_value) is quoted in
+    currency: Option<SnapCurrency>,
+    // (units + fractional_units) * price, filled in after parsing
+    #[serde(default)]
+    market_value: f64,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize)]
+pub struct EnrichedAccount {
+    account: SnapAccount,
+    balances: Vec<SnapBalance>,
+    positions: Vec<SnapPosition>,
+}
 
-    let account_list = accounts.as_array().cloned().unwrap_or_default();
+// Parse one SnapTrade object, naming the endpoint when its shape has changed
+fn parse_snaptrade<T: serde::de::DeserializeOwned>(what: &str, value: serde_json::Value) -> Result<T, String> {
+    serde_json::from_value(value).map_err(|e| format!("{}: unexpected response shape: {}", what, e))
+}
+
+#[tauri::command]
+async fn fetch_snaptrade_accounts(
+    client_id: String,
+    consumer_key: String,
+    user_id: String,
+    user_secret: String,
+) -> Result<Vec<EnrichedAccount>, String> {
+    let auth = SnapTradeAuth {
+        client_id: &client_id,
+        consumer_key: &consumer_key,
+        user_id: &user_id,
+        user_secret: &user_secret,
+    };
+
+    let client = http_client();
+
+    // Fetch accounts list — each path gets its own signature
+    let account_list = snaptrade_get_all(client, &auth, "/api/v1/accounts")
+        .await
+        .map_err(|e| format!("accounts: {}", e))?;
 
     // For each account, fetch balances + positions in parallel
-    let mut enriched: Vec<serde_json::Value> = Vec::new();
+    let mut enriched: Vec<EnrichedAccount> = Vec::new();
     for acct in account_list {
-        let acct_id = acct["id"].as_str().unwrap_or("").to_string();
-        if acct_id.is_empty() {
-            enriched.push(serde_json::json!({
-                "account": acct,
-                "balances": [],
-                "positions": [],
-            }));
+        let account: SnapAccount = parse_snaptrade("accounts", acct)?;
+        if account.id.is_empty() {
+            enriched.push(EnrichedAccount {
+                account,
+                balances: Vec::new(),
+                positions: Vec::new(),
+            });
             continue;
         }
 
-        let balances_path = format!("/api/v1/accounts/{}/balances", acct_id);
-        let positions_path = format!("/api/v1/accounts/{}/positions", acct_id);
-
-        let balances_url = format!("https://api.snaptrade.com{}?{}", balances_path, query_string);
-        let positions_url = format!("https://api.snaptrade.com{}?{}", positions_path, query_string);
-
-        let balances_sig = make_sig(&balances_path)?;
-        let positions_sig = make_sig(&positions_path)?;
+        let balances_path = format!("/api/v1/accounts/{}/balances", account.id);
+        let positions_path = format!("/api/v1/accounts/{}/positions", account.id);
 
+        // Positions paginate for large accounts; balances come back in one response
         let (bal_res, pos_res) = tokio::join!(
-            client
-                .get(&balances_url)
-                .header("Client-Id", &client_id)
-                .header("Timestamp", &timestamp)
-                .header("Signature", &balances_sig)
-                .header("Accept", "application/json")
-                .send(),
-            client
-                .get(&positions_url)
-                .header("Client-Id", &client_id)
-                .header("Timestamp", &timestamp)
-                .header("Signature", &positions_sig)
-                .header("Accept", "application/json")
-                .send()
+            snaptrade_get(client, &auth, &balances_path, &[]),
+            snaptrade_get_all(client, &auth, &positions_path)
         );
 
-        let balances: serde_json::Value = match bal_res {
-            Ok(r) if r.status().is_success() => r.json().await.unwrap_or(serde_json::json!([])),
-            Ok(r) => {
-                eprintln!("balances HTTP {}", r.status());
-                serde_json::json!([])
-            }
+        let balances: Vec<SnapBalance> = match bal_res {
+            Ok(value) => parse_snaptrade("balances", value)?,
             Err(e) => {
-                eprintln!("balances fetch error: {}", e);
-                serde_json::json!([])
+                eprintln!("balances: {}", e);
+                Vec::new()
             }
         };
 
-        let positions: serde_json::Value = match pos_res {
-            Ok(r) if r.status().is_success() => r.json().await.unwrap_or(serde_json::json!([])),
-            Ok(r) => {
-                eprintln!("positions HTTP {}", r.status());
-                serde_json::json!([])
-            }
+        let mut positions: Vec<SnapPosition> = match pos_res {
+            Ok(values) => values
+                .into_iter()
+                .map(|v| parse_snaptrade("positions", v))
+                .collect::<Result<_, _>>()?,
             Err(e) => {
-                eprintln!("positions fetch error: {}", e);
-                serde_json::json!([])
+                eprintln!("positions: {}", e);
+                Vec::new()
             }
         };
+        for position in &mut positions {
+            let units = position.units.unwrap_or(0.0) + position.fractional_units.unwrap_or(0.0);
+            position.market_value = units * position.price.unwrap_or(0.0);
+        }
 
-        enriched.push(serde_json::json!({
-            "account": acct,
-            "balances": balances,
-            "positions": positions,
-        }));
+        enriched.push(EnrichedAccount {
+            account,
+            balances,
+            positions,
+        });
     }
 
-    serde_json::to_string(&enriched)
-        .map_err(|e| format!("JSON serialization error: {}", e))
+    Ok(enriched)
 }
 
 // ─── Fidelity CSV Import ──────────────────────────────────────────────────────
@@ -777,6 +4186,9 @@ struct FidelityPosition {
     total_gain_loss: f64,
     #[serde(rename = "avgCostBasis")]
     avg_cost_basis: f64,
+    // Percent return on cost basis; 0 when there's no basis (e.g. cash)
+    #[serde(rename = "gainLossPercent")]
+    gain_loss_percent: f64,
     #[serde(rename = "isCash")]
     is_cash: bool,
 }
@@ -795,26 +4207,39 @@ fn parse_money(s: &str) -> f64 {
     cleaned.trim().parse::<f64>().unwrap_or(0.0)
 }
 
+// Parse the newest Portfolio_Positions_*.csv export in dir, else dashboard.finance.fidelityDir
+// (default ~/Downloads)
 #[tauri::command]
-fn read_fidelity_csv() -> Result<String, String> {
-    // Look for CSV files in known path
-    let home = std::env::var("HOME").unwrap_or_default();
-    let data_dir = PathBuf::from(&home).join("projects/dashboard-app/src/data");
+fn read_fidelity_csv(dir: Option<String>) -> Result<String, String> {
+    let accounts = load_fidelity_accounts(dir)?;
+    serde_json::to_string(&accounts).map_err(|e| format!("JSON error: {}", e))
+}
+
+fn load_fidelity_accounts(dir: Option<String>) -> Result<Vec<FidelityAccountRaw>, String> {
+    let data_dir = match dir {
+        Some(d) if !d.trim().is_empty() => expand_home(d.trim()),
+        _ => finance_config().fidelity_dir,
+    };
 
-    let mut csv_path: Option<PathBuf> = None;
+    // Newest by modification time; export names don't sort by date reliably
+    let mut csv_path: Option<(std::time::SystemTime, PathBuf)> = None;
     if let Ok(entries) = fs::read_dir(&data_dir) {
         for entry in entries.flatten() {
             let name = entry.file_name().to_string_lossy().to_string();
             if name.starts_with("Portfolio_Positions_") && name.ends_with(".csv") {
-                // Pick the latest one alphabetically
-                if csv_path.as_ref().map_or(true, |p| entry.path() > *p) {
-                    csv_path = Some(entry.path());
+                let modified = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .unwrap_or(std::time::UNIX_EPOCH);
+                if csv_path.as_ref().map_or(true, |(newest, _)| modified > *newest) {
+                    csv_path = Some((modified, entry.path()));
                 }
             }
         }
     }
 
-    let csv_path = csv_path.ok_or("No Portfolio_Positions_*.csv found in src/data/")?;
+    let (_, csv_path) = csv_path
+        .ok_or_else(|| format!("No Portfolio_Positions_*.csv found in {}", data_dir.display()))?;
     let content = fs::read_to_string(&csv_path)
         .map_err(|e| format!("Failed to read CSV: {}", e))?;
 
@@ -823,35 +4248,68 @@ fn read_fidelity_csv() -> Result<String, String> {
 
     let mut accounts: Vec<(String, FidelityAccountRaw)> = Vec::new();
 
-    for (i, line) in content.lines().enumerate() {
-        if i == 0 { continue; } // skip header
-        let line = line.trim();
-        if line.is_empty() { continue; }
+    // Quoted fields may contain commas (e.g. "APPLE INC, COM"); footer disclaimer
+    // lines parse as short records and are skipped below
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
 
-        // Skip footer disclaimer lines — they start with " or don't have enough commas
-        if line.starts_with('"') || line.starts_with("The data") || line.starts_with("Brokerage") || line.starts_with("Date downloaded") {
+    // Fidelity reorders and adds columns now and then, so look them up by header name
+    let headers = reader.headers().map_err(|e| format!("Failed to read CSV header: {}", e))?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Fidelity CSV is missing the \"{}\" column", name))
+    };
+    let account_number_col = column("Account Number")?;
+    let account_name_col = column("Account Name")?;
+    let symbol_col = column("Symbol")?;
+    let description_col = column("Description")?;
+    let quantity_col = column("Quantity")?;
+    let last_price_col = column("Last Price")?;
+    let current_value_col = column("Current Value")?;
+    let gain_loss_col = column("Total Gain/Loss Dollar")?;
+    let cost_basis_col = column("Average Cost Basis")?;
+    let min_len = [
+        account_number_col, account_name_col, symbol_col, description_col, quantity_col,
+        last_price_col, current_value_col, gain_loss_col, cost_basis_col,
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or(0)
+        + 1;
+
+    for record in reader.records() {
+        let Ok(cols) = record else { continue };
+        if cols.len() < min_len { continue; }
+        let account_number = cols[account_number_col].to_string();
+        if account_number.starts_with("The data") || account_number.starts_with("Brokerage") || account_number.starts_with("Date downloaded") {
             continue;
         }
 
-        // Parse CSV (simple split — no quoted commas in this data except description which won't have commas)
-        let cols: Vec<&str> = line.split(',').collect();
-        if cols.len() < 16 { continue; }
-
-        let account_number = cols[0].trim().to_string();
-        let account_name = cols[1].trim().to_string();
-        let symbol = cols[2].trim().to_string();
-        let description = cols[3].trim().to_string();
+        let account_name = cols[account_name_col].to_string();
+        let symbol = cols[symbol_col].to_string();
+        let description = cols[description_col].to_string();
 
         // Skip if account_number looks invalid
         if account_number.is_empty() || account_name.is_empty() {
             continue;
         }
 
-        let quantity = parse_money(cols[4]);
-        let last_price = parse_money(cols[5]);
-        let current_value = parse_money(cols[7]);
-        let total_gain_loss = parse_money(cols[10]);
-        let avg_cost_basis = parse_money(cols[14]);
+        let quantity = parse_money(&cols[quantity_col]);
+        let last_price = parse_money(&cols[last_price_col]);
+        let current_value = parse_money(&cols[current_value_col]);
+        let total_gain_loss = parse_money(&cols[gain_loss_col]);
+        let avg_cost_basis = parse_money(&cols[cost_basis_col]);
+
+        let cost_basis = avg_cost_basis * quantity;
+        let gain_loss_percent = if cost_basis.abs() > f64::EPSILON {
+            (total_gain_loss / cost_basis) * 100.0
+        } else {
+            0.0
+        };
 
         let is_cash = symbol.contains("SPAXX") || symbol.contains("FDRXX") ||
             description.to_uppercase().contains("MONEY MARKET");
@@ -864,6 +4322,7 @@ fn read_fidelity_csv() -> Result<String, String> {
             current_value,
             total_gain_loss,
             avg_cost_basis,
+            gain_loss_percent,
             is_cash,
         };
 
@@ -879,14 +4338,202 @@ fn read_fidelity_csv() -> Result<String, String> {
         }
     }
 
-    let result: Vec<&FidelityAccountRaw> = accounts.iter().map(|(_, v)| v).collect();
-    serde_json::to_string(&result).map_err(|e| format!("JSON error: {}", e))
+    Ok(accounts.into_iter().map(|(_, v)| v).collect())
+}
+
+// ─── Holdings ────────────────────────────────────────────────────────────────
+
+// One line of any balance source, in USD. Each source has an adapter below; the raw
+// per-source commands stay for anything that needs provider-specific fields.
+#[derive(Serialize)]
+pub struct Holding {
+    source: String,
+    account: String,
+    symbol: String,
+    quantity: f64,
+    price: f64,
+    value: f64,
+    #[serde(rename = "isCash")]
+    is_cash: bool,
+}
+
+// Holdings from a cached balances file in finance_dir (Coinbase and Strike share a shape)
+fn cached_balance_holdings(source: &str, file: &str) -> Result<Vec<Holding>, String> {
+    let path = finance_dir().join(file);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    let holdings = json["holdings"]
+        .as_array()
+        .ok_or_else(|| format!("{} has no holdings", path.display()))?;
+
+    Ok(holdings
+        .iter()
+        .map(|h| {
+            let symbol = h["currency"].as_str().unwrap_or("").to_string();
+            Holding {
+                source: source.to_string(),
+                account: source.to_string(),
+                is_cash: matches!(symbol.as_str(), "USD" | "USDC"),
+                symbol,
+                quantity: h["balance"].as_f64().unwrap_or(0.0),
+                price: h["price_usd"].as_f64().unwrap_or(0.0),
+                value: h["usd_value"].as_f64().unwrap_or(0.0),
+            }
+        })
+        .collect())
+}
+
+// Positions plus each account's USD cash, using the credentials in dashboard.finance.snaptrade
+// ({clientId, consumerKey, userId, userSecret}). Holdings are USD by definition and there's
+// no FX rate to hand, so positions and cash in any other currency are left out and described
+// in the second list instead.
+async fn snaptrade_holdings() -> Result<SourceHoldings, String> {
+    let settings = dashboard_setting("finance")["snaptrade"].clone();
+    let field = |key: &str| {
+        settings[key]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("dashboard.finance.snaptrade.{} not configured", key))
+    };
+    let accounts = fetch_snaptrade_accounts(
+        field("clientId")?,
+        field("consumerKey")?,
+        field("userId")?,
+        field("userSecret")?,
+    )
+    .await?;
+
+    let mut holdings = Vec::new();
+    let mut left_out = Vec::new();
+    let code = |c: &Option<SnapCurrency>| c.as_ref().map_or("unknown currency", |c| c.code.as_str()).to_string();
+    for acct in &accounts {
+        let account = acct
+            .account
+            .name
+            .clone()
+            .or_else(|| acct.account.number.clone())
+            .unwrap_or_else(|| acct.account.id.clone());
+        for p in &acct.positions {
+            let Some(symbol) = p.symbol.as_ref().and_then(|s| s.symbol.as_ref()) else { continue };
+            let currency = code(&p.currency);
+            if currency != "USD" {
+                left_out.push(format!(
+                    "{}: {} ({:.2} {}) left out, not in USD",
+                    account, symbol.symbol, p.market_value, currency
+                ));
+                continue;
+            }
+            holdings.push(Holding {
+                source: "SnapTrade".to_string(),
+                account: account.clone(),
+                symbol: symbol.symbol.clone(),
+                quantity: p.units.unwrap_or(0.0) + p.fractional_units.unwrap_or(0.0),
+                price: p.price.unwrap_or(0.0),
+                value: p.market_value,
+                is_cash: false,
+            });
+        }
+        let mut cash = 0.0;
+        for balance in &acct.balances {
+            let amount = balance.cash.unwrap_or(0.0);
+            match code(&balance.currency) {
+                c if c == "USD" => cash += amount,
+                _ if amount == 0.0 => {}
+                c => left_out.push(format!("{}: {:.2} {} cash left out, not in USD", account, amount, c)),
+            }
+        }
+        if cash != 0.0 {
+            holdings.push(Holding {
+                source: "SnapTrade".to_string(),
+                account,
+                symbol: "USD".to_string(),
+                quantity: cash,
+                price: 1.0,
+                value: cash,
+                is_cash: true,
+            });
+        }
+    }
+    Ok((holdings, left_out))
+}
+
+fn fidelity_holdings() -> Result<Vec<Holding>, String> {
+    Ok(load_fidelity_accounts(None)?
+        .into_iter()
+        .flat_map(|acct| {
+            let account = acct.account_name;
+            acct.positions.into_iter().map(move |p| Holding {
+                source: "Fidelity".to_string(),
+                account: account.clone(),
+                symbol: p.symbol,
+                quantity: p.quantity,
+                price: p.last_price,
+                value: p.current_value,
+                is_cash: p.is_cash,
+            })
+        })
+        .collect())
+}
+
+const HOLDING_SOURCES: [&str; 4] = ["Coinbase", "Strike", "SnapTrade", "Fidelity"];
+
+// (USD holdings, descriptions of anything left out because it couldn't be valued in USD)
+type SourceHoldings = (Vec<Holding>, Vec<String>);
+
+async fn source_holdings(source: &str) -> Result<SourceHoldings, String> {
+    let all_usd = |holdings: Vec<Holding>| (holdings, Vec::new());
+    match source.to_lowercase().as_str() {
+        "coinbase" => cached_balance_holdings("Coinbase", "coinbase-balances.json").map(all_usd),
+        "strike" => cached_balance_holdings("Strike", "strike-balances.json").map(all_usd),
+        "snaptrade" => snaptrade_holdings().await,
+        "fidelity" => fidelity_holdings().map(all_usd),
+        _ => Err(format!("Unknown source: {} (expected one of {})", source, HOLDING_SOURCES.join(", "))),
+    }
+}
+
+// Holdings for one source: coinbase, strike, snaptrade or fidelity
+#[tauri::command]
+async fn get_holdings(source: String) -> Result<Vec<Holding>, String> {
+    source_holdings(&source).await.map(|(holdings, _)| holdings)
+}
+
+// ─── Net worth ───────────────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct NetWorth {
+    total_usd: f64,
+    // (source, USD value) for every source that loaded
+    sources: Vec<(String, f64)>,
+    // (source, error) for sources, or non-USD parts of them, left out of the total
+    errors: Vec<(String, String)>,
+}
+
+// One total across every holdings source, all in USD. A source that can't be loaded is
+// listed in errors and left out rather than failing the whole total.
+#[tauri::command]
+async fn get_net_worth() -> Result<NetWorth, String> {
+    let mut worth = NetWorth { total_usd: 0.0, sources: Vec::new(), errors: Vec::new() };
+    for source in HOLDING_SOURCES {
+        match source_holdings(source).await {
+            Ok((holdings, left_out)) => {
+                let usd: f64 = holdings.iter().map(|h| h.value).sum();
+                worth.total_usd += usd;
+                worth.sources.push((source.to_string(), usd));
+                worth.errors.extend(left_out.into_iter().map(|e| (source.to_string(), e)));
+            }
+            Err(e) => worth.errors.push((source.to_string(), e)),
+        }
+    }
+    Ok(worth)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .setup(|app| {
+            start_stats_sampler();
             if cfg!(debug_assertions) {
                 app.handle().plugin(
                     tauri_plugin_log::Builder::default()
@@ -896,7 +4543,13 @@ pub fn run() {
             }
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![get_system_stats, get_projects, toggle_task, get_gateway_config, toggle_input_mute, start_voice_input, stop_voice_input, speak_text, fetch_tickers, fetch_coinbase, read_coinbase_data, fetch_strike, read_strike_data, fetch_snaptrade_accounts, read_fidelity_csv, fetch_metals_spots])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![get_system_stats, get_system_stats_v2, start_stats_stream, stop_stats_stream, get_top_processes, get_battery, get_gpu_stats, get_temperatures, get_cpu_history, get_disk_trend, get_home_disk, get_projects, watch_projects, get_task_counts, get_category_progress, get_task_summary, find_duplicate_projects, get_project_graph, get_prioritized_tasks, search_projects, get_recent_activity, toggle_task, toggle_task_by_text, add_task, delete_task, reorder_task, edit_task, add_tasks_bulk, defer_task, create_project, archive_project, unarchive_project, rename_project, get_gateway_config, get_openclaw_config, set_gateway_config, edit_config, get_locale_info, start_gateway_ping, stop_gateway_ping, get_gateway_latency_history, check_gateway, backup_workspace, restore_workspace, toggle_input_mute, toggle_input_mute_relative, get_input_mute, start_voice_input, stop_voice_input, is_recording, list_whisper_models, recover_recordings, transcribe_recording, discard_recording, save_voice_note, speak_text, synthesize_text, list_tts_voices, stop_speaking, get_speech_status, fetch_tickers, fetch_ticker_history, get_top_movers, fetch_symbol_meta, fetch_coinbase, fetch_coinbase_native, read_coinbase_data, get_coinbase, fetch_strike, read_strike_data, get_strike, fetch_snaptrade_accounts, read_fidelity_csv, get_holdings, get_net_worth, fetch_metals_spots])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Stop the projects watcher thread before the process goes away
+                PROJECTS_WATCHER.lock().unwrap().take();
+            }
+        });
 }
@@ -5,12 +5,25 @@ import { invoke } from '@tauri-apps/api/core'
 import { useGatewayChat, type ImageAttachment } from './useGatewayChat'
 
 interface Ticker {
+  status: 'ok'
   symbol: string
   label: string
   price: string
+  price_raw: number
   change: number
+  trend: 'up' | 'down' | 'flat'
 }
 
+interface TickerError {
+  status: 'error'
+  symbol: string
+  label: string
+  icon: string
+  error: string
+}
+
+type TickerResult = Ticker | TickerError
+
 interface Weather {
   temp: number
   condition: string
@@ -26,6 +39,11 @@ interface SystemStats {
 interface Task {
   text: string
   done: boolean
+  depth: number
+  due: string | null
+  overdue: boolean
+  priority: number | null
+  tags: string[]
 }
 
 interface Project {
@@ -36,7 +54,10 @@ interface Project {
   description: string
   task_count: number
   tasks_done: number
+  progress: number
+  completed: boolean
   tasks: Task[]
+  modified: number
 }
 
 function App() {
@@ -51,7 +72,7 @@ function App() {
   const [pendingImages, setPendingImages] = useState<ImageAttachment[]>([])
   const fileInputRef = useRef<HTMLInputElement>(null)
   const [isListening, setIsListening] = useState(false)
-  const [tickers, setTickers] = useState<Ticker[]>([])
+  const [tickers, setTickers] = useState<TickerResult[]>([])
   const chatContainerRef = useRef<HTMLDivElement>(null)
   
   const selectedProject = projects.find(p => p.id === selectedProjectId) || projects[0]
@@ -160,6 +181,26 @@ function App() {
       .catch(err => console.error('Weather fetch failed:', err))
   }, [])
 
+  // Offer recordings a crash left behind: transcribe into the chat box, or discard
+  useEffect(() => {
+    const recoverRecordings = async () => {
+      try {
+        const paths = await invoke<string[]>('recover_recordings')
+        for (const path of paths) {
+          if (window.confirm('Found an unfinished voice recording. Transcribe it?')) {
+            const transcript = await invoke<string>('transcribe_recording', { path })
+            if (transcript) setChatInput(prev => (prev ? `${prev} ${transcript}` : transcript))
+          }
+          await invoke('discard_recording', { path })
+        }
+      } catch (err) {
+        console.error('Failed to recover recordings:', err)
+      }
+    }
+
+    recoverRecordings()
+  }, [])
+
   // Fetch system stats every 2 seconds
   useEffect(() => {
     const fetchStats = async () => {
@@ -180,7 +221,7 @@ function App() {
   useEffect(() => {
     const fetchTickers = async () => {
       try {
-        const data = await invoke<Ticker[]>('fetch_tickers')
+        const data = await invoke<TickerResult[]>('fetch_tickers')
         if (data.length > 0) setTickers(data)
       } catch (err) {
         console.error('Ticker fetch failed:', err)
@@ -234,13 +275,33 @@ function App() {
       {/* Ticker Bar */}
       {tickers.length > 0 && (
         <div className="glass px-4 py-3 mb-6 flex items-center justify-center gap-8">
-          {tickers.map((t) => (
+          {tickers.map((t) => t.status === 'error' ? (
+            <button
+              key={t.label}
+              title={`${t.error} (click to retry)`}
+              onClick={async () => {
+                try {
+                  const [fresh] = await invoke<TickerResult[]>('fetch_tickers', {
+                    symbols: [{ symbol: t.symbol, label: t.label, icon: t.icon }],
+                    force: true,
+                  })
+                  if (fresh) setTickers((prev) => prev.map((p) => (p.label === t.label ? fresh : p)))
+                } catch (err) {
+                  console.error('Ticker retry failed:', err)
+                }
+              }}
+              className="flex items-center gap-2 text-red-400"
+            >
+              <span className="text-lg">{t.icon}</span>
+              <span className="text-sm font-medium">{t.label}: fetch failed</span>
+            </button>
+          ) : (
             <div key={t.label} className="flex items-center gap-2">
               <span className="text-lg">{t.symbol}</span>
               <span className="text-white/60 text-sm font-medium">{t.label}</span>
               <span className="text-white/90 font-semibold">{t.price}</span>
-              <span className={`text-sm font-medium ${t.change >= 0 ? 'text-green-400' : 'text-red-400'}`}>
-                {t.change >= 0 ? '▲' : '▼'} {Math.abs(t.change).toFixed(1)}%
+              <span className={`text-sm font-medium ${t.trend === 'up' ? 'text-green-400' : t.trend === 'down' ? 'text-red-400' : 'text-white/50'}`}>
+                {t.trend === 'up' ? '▲' : t.trend === 'down' ? '▼' : '–'} {Math.abs(t.change).toFixed(1)}%
               </span>
             </div>
           ))}
@@ -287,8 +348,8 @@ function App() {
                   ? project.category.toLowerCase() === 'business'
                   : project.category.toLowerCase() !== 'business'
               })
-              const active = filtered.filter(p => !(p.task_count > 0 && p.tasks_done === p.task_count))
-              const completed = filtered.filter(p => p.task_count > 0 && p.tasks_done === p.task_count)
+              const active = filtered.filter(p => !p.completed)
+              const completed = filtered.filter(p => p.completed)
               
               return filtered.length > 0 ? (
                 <>
@@ -347,6 +408,7 @@ function App() {
                     key={task.origIndex} 
                     text={task.text} 
                     done={task.done} 
+                    depth={task.depth}
                     onToggle={async () => {
                       try {
                         await invoke('toggle_task', { projectId: selectedProject.id, taskIndex: task.origIndex })
@@ -590,10 +652,11 @@ function ProjectCard({ id, name, status, description, tasks, selected, onClick }
   )
 }
 
-function TaskItem({ text, done, onToggle }: { text: string; done: boolean; onToggle?: () => void }) {
+function TaskItem({ text, done, depth = 0, onToggle }: { text: string; done: boolean; depth?: number; onToggle?: () => void }) {
   return (
     <div 
       className="flex items-center gap-3 text-lg cursor-pointer hover:bg-white/5 rounded-lg px-2 py-1 -mx-2 transition-colors"
+      style={depth > 0 ? { marginLeft: `calc(${depth * 1.5}rem - 0.5rem)` } : undefined}
       onClick={onToggle}
     >
       <div className={`w-5 h-5 shrink-0 rounded-md border ${
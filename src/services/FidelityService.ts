@@ -8,6 +8,7 @@ export interface FidelityPosition {
   currentValue: number
   totalGainLoss: number
   avgCostBasis: number
+  gainLossPercent: number
   isCash: boolean
 }
 
@@ -39,6 +39,7 @@ export interface SnapPosition {
   open_pnl: number
   fractional_units?: number
   average_purchase_price: number
+  market_value: number
 }
 
 export interface BrokerageAccount {
@@ -66,7 +67,8 @@ export interface BrokeragePosition {
 
 // ─── Rust backend call ────────────────────────────────────────────────────────
 // The Rust `fetch_snaptrade_accounts` command signs requests server-side and
-// returns a JSON string: Array<{ account, balances, positions }>.
+// returns Array<{ account, balances, positions }>, already checked against the
+// fields typed below. Positions also carry a computed `market_value`.
 
 interface RawEnriched {
   account: SnapAccount
@@ -79,9 +81,9 @@ interface RawEnriched {
 export async function loadBrokerageAccounts(): Promise<BrokerageAccount[]> {
   const { clientId, consumerKey, userId, userSecret } = snapConfig
 
-  let jsonStr: string
+  let enriched: RawEnriched[]
   try {
-    jsonStr = await invoke<string>('fetch_snaptrade_accounts', {
+    enriched = await invoke<RawEnriched[]>('fetch_snaptrade_accounts', {
       clientId,
       consumerKey,
       userId,
@@ -91,7 +93,6 @@ export async function loadBrokerageAccounts(): Promise<BrokerageAccount[]> {
     throw new Error(String(e))
   }
 
-  const enriched: RawEnriched[] = JSON.parse(jsonStr)
   if (!enriched || enriched.length === 0) return []
 
   return enriched.map((item): BrokerageAccount => {
@@ -108,7 +109,7 @@ export async function loadBrokerageAccounts(): Promise<BrokerageAccount[]> {
       .filter(p => p && p.symbol?.symbol?.symbol)
       .map(p => {
         const shares = (p.units ?? 0) + (p.fractional_units ?? 0)
-        const marketValue = shares * p.price
+        const marketValue = p.market_value
         const avgCost = p.average_purchase_price ?? 0
         const costBasis = avgCost * shares
         const gainLoss = marketValue - costBasis
@@ -0,0 +1,8 @@
+import { convertFileSrc, invoke } from '@tauri-apps/api/core'
+
+// Synthesize text to a temp WAV and return a URL an <audio> element can play.
+// The asset protocol is scoped to these temp files in tauri.conf.json.
+export async function synthesizeSpeechUrl(text: string, voice?: string, speed?: number): Promise<string> {
+  const path = await invoke<string>('synthesize_text', { text, voice, speed })
+  return convertFileSrc(path)
+}